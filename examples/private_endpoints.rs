@@ -1,6 +1,7 @@
+use chrono::{NaiveDate, NaiveTime};
 use osars::{
     CallRequest, Client, CreateParserRequest, LessonRequest, UpdateCallsRequest,
-    UpdateGroupsRequest, UpdateLessonsRequest,
+    UpdateGroupsRequest, UpdateLessonsRequest, Weekday,
 };
 
 #[tokio::main]
@@ -20,10 +21,23 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     };
 
     let response = admin_api.create_parser(create_request).await?;
-    println!("Created parser with token: {}", response.token);
+    println!(
+        "Created parser {} with token: {}",
+        response.id, response.token
+    );
+
+    // Inspect the parser and issue a new token
+    let parser = admin_api.parser(response.id).await?;
+    println!(
+        "{} has {} campuses",
+        parser.college_name,
+        parser.campuses.len()
+    );
+    let rotated = admin_api.rotate_token(response.id).await?;
+    println!("New token: {}", rotated.token);
 
     // Remove parser
-    admin_api.delete_parser(123).await?;
+    admin_api.delete_parser(response.id).await?;
     println!("Parser deleted");
 
     // Work with parser
@@ -38,25 +52,27 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Update call schedule
     let calls_request = UpdateCallsRequest {
-        calls: vec![CallRequest {
-            weekday: 1,
-            begins: "09:00:00".to_string(),
-            ends: "10:30:00".to_string(),
-            order: 1,
-        }],
+        calls: vec![CallRequest::new(
+            Weekday::Monday,
+            NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+            1,
+        )],
     };
     parser_api.update_calls(calls_request).await?;
 
     // Add lessons
     let lessons_request = UpdateLessonsRequest {
-        lessons: vec![LessonRequest {
-            group_id: 1,
-            order: 1,
-            title: "Mathematics".to_string(),
-            teacher: "Dr. Smith".to_string(),
-            cabinet: "Room 101".to_string(),
-            date: "2023-10-01".to_string(),
-        }],
+        lessons: vec![
+            LessonRequest::builder()
+                .group_id(1)
+                .order(1)
+                .title("Mathematics")
+                .teacher("Dr. Smith")
+                .cabinet("Room 101")
+                .date(NaiveDate::from_ymd_opt(2023, 10, 1).unwrap())
+                .build()?,
+        ],
     };
     parser_api.add_lessons(lessons_request).await?;
 
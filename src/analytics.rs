@@ -0,0 +1,220 @@
+use crate::plan::{ACADEMIC_HOUR_MINUTES, DayPlan};
+use crate::{Call, Lesson, Schedule, Weekday};
+use chrono::NaiveTime;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Workload statistics over a set of schedules.
+///
+/// Hours are academic hours computed from each lesson's own start and end time.
+/// Windows are counted with [`DayPlan::windows`], so they need the college's
+/// calls; without calls no windows are reported.
+///
+/// # Examples
+///
+/// ```
+/// use osars::analytics::ScheduleReport;
+///
+/// let report = ScheduleReport::new(&[], &[]);
+/// assert_eq!(report.lessons, 0);
+/// assert_eq!(report.to_csv(), "metric,key,value\n");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ScheduleReport {
+    /// Total number of lessons
+    pub lessons: usize,
+    pub hours_by_subject: BTreeMap<String, f64>,
+    pub hours_by_teacher: BTreeMap<String, f64>,
+    pub hours_by_cabinet: BTreeMap<String, f64>,
+    pub hours_by_group: BTreeMap<u32, f64>,
+    pub lessons_by_weekday: BTreeMap<Weekday, usize>,
+    /// Bell periods without lessons between the first and last lesson of a day, summed per group
+    pub windows_by_group: BTreeMap<u32, usize>,
+    pub earliest_start: Option<NaiveTime>,
+    pub latest_end: Option<NaiveTime>,
+}
+
+impl ScheduleReport {
+    pub fn new(schedules: &[Schedule], calls: &[Call]) -> Self {
+        let mut report = Self::default();
+
+        for schedule in schedules {
+            let weekday = Weekday::of(schedule.date);
+            let windows = report
+                .windows_by_group
+                .entry(schedule.group_id)
+                .or_default();
+            *windows += DayPlan::new(schedule, calls).windows().len();
+
+            for lesson in &schedule.lessons {
+                let hours = academic_hours(lesson);
+                report.lessons += 1;
+                *report
+                    .hours_by_subject
+                    .entry(lesson.title.clone())
+                    .or_default() += hours;
+                *report
+                    .hours_by_teacher
+                    .entry(lesson.teacher.clone())
+                    .or_default() += hours;
+                *report
+                    .hours_by_cabinet
+                    .entry(lesson.cabinet.clone())
+                    .or_default() += hours;
+                *report.hours_by_group.entry(schedule.group_id).or_default() += hours;
+                *report.lessons_by_weekday.entry(weekday).or_default() += 1;
+
+                report.earliest_start = Some(match report.earliest_start {
+                    Some(t) => t.min(lesson.start_time),
+                    None => lesson.start_time,
+                });
+                report.latest_end = Some(match report.latest_end {
+                    Some(t) => t.max(lesson.end_time),
+                    None => lesson.end_time,
+                });
+            }
+        }
+
+        report
+    }
+
+    /// Flattens the report into `metric,key,value` rows.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("metric,key,value\n");
+        let mut row = |metric: &str, key: &str, value: String| {
+            out.push_str(&format!(
+                "{},{},{}\n",
+                csv_field(metric),
+                csv_field(key),
+                csv_field(&value)
+            ));
+        };
+
+        if self.lessons > 0 {
+            row("lessons", "", self.lessons.to_string());
+        }
+        for (subject, hours) in &self.hours_by_subject {
+            row("hours_by_subject", subject, hours.to_string());
+        }
+        for (teacher, hours) in &self.hours_by_teacher {
+            row("hours_by_teacher", teacher, hours.to_string());
+        }
+        for (cabinet, hours) in &self.hours_by_cabinet {
+            row("hours_by_cabinet", cabinet, hours.to_string());
+        }
+        for (group, hours) in &self.hours_by_group {
+            row("hours_by_group", &group.to_string(), hours.to_string());
+        }
+        for (weekday, lessons) in &self.lessons_by_weekday {
+            row(
+                "lessons_by_weekday",
+                &weekday.to_string(),
+                lessons.to_string(),
+            );
+        }
+        for (group, windows) in &self.windows_by_group {
+            row("windows_by_group", &group.to_string(), windows.to_string());
+        }
+        if let Some(time) = self.earliest_start {
+            row("earliest_start", "", time.format("%H:%M").to_string());
+        }
+        if let Some(time) = self.latest_end {
+            row("latest_end", "", time.format("%H:%M").to_string());
+        }
+
+        out
+    }
+}
+
+fn academic_hours(lesson: &Lesson) -> f64 {
+    (lesson.end_time - lesson.start_time).num_minutes() as f64 / ACADEMIC_HOUR_MINUTES as f64
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn lesson(order: u32, title: &str, teacher: &str, hour: u32) -> Lesson {
+        Lesson {
+            title: title.to_string(),
+            cabinet: "101".to_string(),
+            teacher: teacher.to_string(),
+            order,
+            start_time: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(hour + 1, 30, 0).unwrap(),
+        }
+    }
+
+    fn calls() -> Vec<Call> {
+        (1..=3)
+            .map(|order| Call {
+                call_id: order,
+                weekday: Weekday::Monday,
+                begins: NaiveTime::from_hms_opt(6 + order * 2, 0, 0).unwrap(),
+                ends: NaiveTime::from_hms_opt(7 + order * 2, 30, 0).unwrap(),
+                order,
+            })
+            .collect()
+    }
+
+    fn schedules() -> Vec<Schedule> {
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        vec![
+            Schedule {
+                group_id: 1,
+                date: monday,
+                lessons: vec![
+                    lesson(1, "Math", "Smith", 8),
+                    lesson(3, "Physics", "Curie, M.", 12),
+                ],
+            },
+            Schedule {
+                group_id: 2,
+                date: monday.succ_opt().unwrap(),
+                lessons: vec![lesson(2, "Math", "Smith", 10)],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_report() {
+        let report = ScheduleReport::new(&schedules(), &calls());
+
+        assert_eq!(report.lessons, 3);
+        assert_eq!(report.hours_by_subject["Math"], 4.0);
+        assert_eq!(report.hours_by_teacher["Smith"], 4.0);
+        assert_eq!(report.hours_by_cabinet["101"], 6.0);
+        assert_eq!(report.hours_by_group[&1], 4.0);
+        assert_eq!(report.lessons_by_weekday[&Weekday::Monday], 2);
+        assert_eq!(report.windows_by_group[&1], 1);
+        assert_eq!(report.windows_by_group[&2], 0);
+        assert_eq!(
+            ScheduleReport::new(&schedules(), &[]).windows_by_group[&1],
+            0
+        );
+        assert_eq!(report.earliest_start, NaiveTime::from_hms_opt(8, 0, 0));
+        assert_eq!(report.latest_end, NaiveTime::from_hms_opt(13, 30, 0));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["lessons_by_weekday"]["tuesday"], 1);
+    }
+
+    #[test]
+    fn test_csv_escapes_fields() {
+        let csv = ScheduleReport::new(&schedules(), &calls()).to_csv();
+
+        assert!(csv.starts_with("metric,key,value\nlessons,,3\n"));
+        assert!(csv.contains("hours_by_teacher,\"Curie, M.\",2\n"));
+        assert!(csv.contains("lessons_by_weekday,monday,2\n"));
+        assert!(csv.ends_with("latest_end,,13:30\n"));
+    }
+}
@@ -1,4 +1,8 @@
-use crate::{AuthenticatedClient, CreateParserRequest, CreateParserResponse, error::Result};
+use crate::{
+    AddCampusesRequest, AuthenticatedClient, Campus, CreateParserRequest, CreateParserResponse,
+    ParserInfo, RenameCampusRequest, RotateTokenResponse,
+    error::{Error, Result},
+};
 
 pub struct AdminApi {
     client: AuthenticatedClient,
@@ -9,24 +13,176 @@ impl AdminApi {
         Self { client }
     }
 
+    /// List all parsers with their colleges and campuses
+    pub async fn parsers(&self) -> Result<Vec<ParserInfo>> {
+        let path = "/admin/parsers";
+        self.client.get(path).await
+    }
+
+    /// Get a single parser
+    pub async fn parser(&self, parser_id: u32) -> Result<ParserInfo> {
+        let path = format!("/admin/parser/{}", parser_id);
+        self.client.get(&path).await
+    }
+
     /// Create a new parser
+    ///
+    /// The response carries the parser ID and its token. A dry-run client
+    /// records the request and fails with [`Error::DryRun`], as there is no
+    /// parser to return.
     pub async fn create_parser(
         &self,
         request: CreateParserRequest,
     ) -> Result<CreateParserResponse> {
         let path = "/admin/parser";
-        self.client
-            .client
-            .post_json(path, Some(&request), Some(&self.client.auth))
-            .await
+        let response = self.client.post(path, &request).await?;
+        self.sent(path)?;
+        Ok(response)
+    }
+
+    /// Issue a new token for a parser, invalidating the old one
+    ///
+    /// A dry-run client records the request and fails with [`Error::DryRun`].
+    pub async fn rotate_token(&self, parser_id: u32) -> Result<RotateTokenResponse> {
+        let path = format!("/admin/parser/{}/token", parser_id);
+        let response = self.client.post(&path, &()).await?;
+        self.sent(&path)?;
+        Ok(response)
+    }
+
+    /// Add campuses to a parser's college
+    ///
+    /// Returns the created campuses.
+    pub async fn add_campuses(
+        &self,
+        parser_id: u32,
+        request: AddCampusesRequest,
+    ) -> Result<Vec<Campus>> {
+        let path = format!("/admin/parser/{}/campuses", parser_id);
+        self.client.post(&path, &request).await
+    }
+
+    /// Rename a campus
+    pub async fn rename_campus(&self, campus_id: u32, request: RenameCampusRequest) -> Result<()> {
+        let path = format!("/admin/campus/{}", campus_id);
+        self.client.patch(&path, &request).await
     }
 
     /// Delete a parser
     pub async fn delete_parser(&self, parser_id: u32) -> Result<()> {
         let path = format!("/admin/parser/{}", parser_id);
-        self.client
-            .client
-            .delete_json(&path, Some(&self.client.auth))
+        self.client.delete(&path).await
+    }
+
+    /// Fails for POSTs whose response only the server can produce
+    fn sent(&self, path: &str) -> Result<()> {
+        if self.client.is_dry_run() {
+            return Err(Error::DryRun {
+                method: "POST".to_string(),
+                path: path.to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Client, CreateParserRequest, RenameCampusRequest};
+    use mockito::{Matcher, Server};
+
+    #[tokio::test]
+    async fn test_create_inspect_and_rotate() {
+        let mut server = Server::new_async().await;
+        let create = server
+            .mock("POST", "/admin/parser")
+            .match_header("authorization", "Bearer admin")
+            .with_body(r#"{"parserId":7,"token":"first"}"#)
+            .create_async()
+            .await;
+        let inspect = server
+            .mock("GET", "/admin/parser/7")
+            .match_header("authorization", "Bearer admin")
+            .with_body(
+                r#"{"parserId":7,"collegeId":2,"collegeName":"TKPST","campuses":[{"campusId":5,"name":"Main","collegeId":2}]}"#,
+            )
+            .create_async()
+            .await;
+        let rotate = server
+            .mock("POST", "/admin/parser/7/token")
+            .match_header("authorization", "Bearer admin")
+            .with_body(r#"{"token":"second"}"#)
+            .create_async()
+            .await;
+        let list = server
+            .mock("GET", "/admin/parsers")
+            .match_header("authorization", "Bearer admin")
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        let admin = client.authenticated().with_token("admin").admin();
+
+        let created = admin
+            .create_parser(CreateParserRequest {
+                college_name: "TKPST".to_string(),
+                campus_names: vec!["Main".to_string()],
+            })
+            .await
+            .unwrap();
+        let parser = admin.parser(created.id).await.unwrap();
+        let rotated = admin.rotate_token(created.id).await.unwrap();
+        let parsers = admin.parsers().await.unwrap();
+
+        create.assert_async().await;
+        inspect.assert_async().await;
+        rotate.assert_async().await;
+        list.assert_async().await;
+
+        assert_eq!((created.id, created.token.as_str()), (7, "first"));
+        assert_eq!(parser.college_name, "TKPST");
+        assert_eq!(parser.campuses[0].id, 5);
+        assert_eq!(rotated.token, "second");
+        assert!(parsers.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rename_campus_and_missing_parser_id() {
+        let mut server = Server::new_async().await;
+        let rename = server
+            .mock("PATCH", "/admin/campus/5")
+            .match_header("authorization", "Bearer admin")
+            .match_body(Matcher::Json(serde_json::json!({"name": "North"})))
+            .with_status(200)
+            .create_async()
+            .await;
+        server
+            .mock("POST", "/admin/parser")
+            .with_body(r#"{"token":"first"}"#)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        let admin = client.authenticated().with_token("admin").admin();
+
+        admin
+            .rename_campus(
+                5,
+                RenameCampusRequest {
+                    name: "North".to_string(),
+                },
+            )
             .await
+            .unwrap();
+        let created = admin
+            .create_parser(CreateParserRequest {
+                college_name: "TKPST".to_string(),
+                campus_names: vec![],
+            })
+            .await;
+
+        rename.assert_async().await;
+        assert!(matches!(created, Err(crate::Error::Serialization(_))));
     }
 }
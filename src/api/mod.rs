@@ -3,6 +3,8 @@ pub mod colleges;
 pub mod groups;
 pub mod parser;
 pub mod schedules;
+pub mod sync;
+pub mod upload;
 
 pub use admin::AdminApi;
 pub use colleges::CampusQuery;
@@ -12,3 +14,5 @@ pub use colleges::CollegesQuery;
 pub use groups::GroupsQuery;
 pub use parser::ParserApi;
 pub use schedules::ScheduleQuery;
+pub use sync::{LessonSync, SyncPlan};
+pub use upload::{LessonUpload, UploadSummary};
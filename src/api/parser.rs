@@ -1,10 +1,13 @@
+use super::sync::LessonSync;
+use super::upload::LessonUpload;
 use crate::{
-    AuthenticatedClient, UpdateCallsRequest, UpdateGroupsRequest, UpdateLessonsRequest,
-    error::Result,
+    AuthenticatedClient, DeleteCallsRequest, DeleteGroupsRequest, DeleteLessonsRequest,
+    DeleteResponse, LessonRequest, ReplaceLessonsRequest, ReplaceLessonsResponse,
+    UpdateCallsRequest, UpdateGroupsRequest, UpdateLessonsRequest, Validate, error::Result,
 };
 
 pub struct ParserApi {
-    client: AuthenticatedClient,
+    pub(crate) client: AuthenticatedClient,
 }
 
 impl ParserApi {
@@ -13,29 +16,156 @@ impl ParserApi {
     }
 
     /// Update groups for a campus
+    ///
+    /// The request is checked with [`Validate`] before it is sent.
     pub async fn update_groups(&self, request: UpdateGroupsRequest) -> Result<()> {
+        request.validate()?;
         let path = "/parser/groups";
-        self.client
-            .client
-            .post_json(path, Some(&request), Some(&self.client.auth))
-            .await
+        self.client.post(path, &request).await
     }
 
     /// Update call schedule
+    ///
+    /// The request is checked with [`Validate`] before it is sent.
     pub async fn update_calls(&self, request: UpdateCallsRequest) -> Result<()> {
+        request.validate()?;
         let path = "/parser/calls";
-        self.client
-            .client
-            .post_json(path, Some(&request), Some(&self.client.auth))
-            .await
+        self.client.post(path, &request).await
     }
 
     /// Add lessons
+    ///
+    /// The request is checked with [`Validate`] before it is sent.
     pub async fn add_lessons(&self, request: UpdateLessonsRequest) -> Result<()> {
+        request.validate()?;
+        self.send_lessons(&request).await
+    }
+
+    /// Upload a large list of lessons in chunks, see [`LessonUpload`]
+    pub fn upload_lessons(&self, lessons: Vec<LessonRequest>) -> LessonUpload<'_> {
+        LessonUpload::new(self, lessons)
+    }
+
+    /// Replace only the days that differ from the server, see [`LessonSync`]
+    pub fn sync_lessons(&self, lessons: Vec<LessonRequest>) -> LessonSync<'_> {
+        LessonSync::new(self, lessons)
+    }
+
+    /// Delete lessons of a group in a date range, or a single lesson
+    ///
+    /// The request is checked with [`Validate`] before it is sent.
+    pub async fn delete_lessons(&self, request: DeleteLessonsRequest) -> Result<DeleteResponse> {
+        request.validate()?;
         let path = "/parser/lessons";
         self.client
-            .client
-            .post_json(path, Some(&request), Some(&self.client.auth))
+            .delete_with::<Option<_>, _>(path, &request)
+            .await
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Replace all lessons of a group in a date range
+    ///
+    /// The request is checked with [`Validate`] before it is sent.
+    pub async fn replace_lessons(
+        &self,
+        request: ReplaceLessonsRequest,
+    ) -> Result<ReplaceLessonsResponse> {
+        request.validate()?;
+        let path = "/parser/lessons/replace";
+        self.client
+            .post::<Option<_>, _>(path, &request)
+            .await
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Remove groups from a campus
+    ///
+    /// The request is checked with [`Validate`] before it is sent.
+    pub async fn delete_groups(&self, request: DeleteGroupsRequest) -> Result<DeleteResponse> {
+        request.validate()?;
+        let path = "/parser/groups";
+        self.client
+            .delete_with::<Option<_>, _>(path, &request)
+            .await
+            .map(Option::unwrap_or_default)
+    }
+
+    /// Remove calls by order
+    ///
+    /// The request is checked with [`Validate`] before it is sent.
+    pub async fn delete_calls(&self, request: DeleteCallsRequest) -> Result<DeleteResponse> {
+        request.validate()?;
+        let path = "/parser/calls";
+        self.client
+            .delete_with::<Option<_>, _>(path, &request)
+            .await
+            .map(Option::unwrap_or_default)
+    }
+
+    pub(crate) async fn send_lessons(&self, request: &UpdateLessonsRequest) -> Result<()> {
+        let path = "/parser/lessons";
+        self.client.post(path, request).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Client, DeleteLessonsRequest, LessonRequest, ReplaceLessonsRequest};
+    use chrono::NaiveDate;
+    use mockito::{Matcher, Server};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_delete_and_replace_lessons() {
+        let mut server = Server::new_async().await;
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let delete = server
+            .mock("DELETE", "/parser/lessons")
+            .match_body(Matcher::Json(json!({
+                "groupId": 1,
+                "from": "2024-01-15",
+                "to": "2024-01-15",
+                "order": 2
+            })))
+            .with_body(r#"{"deleted":1}"#)
+            .create_async()
+            .await;
+        let replace = server
+            .mock("POST", "/parser/lessons/replace")
+            .match_body(Matcher::PartialJson(
+                json!({"groupId": 1, "to": "2024-01-21"}),
+            ))
+            .with_status(200)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        let parser = client.authenticated().with_token("token").parser();
+
+        let deleted = parser
+            .delete_lessons(DeleteLessonsRequest::lesson(1, date, 2))
             .await
+            .unwrap();
+        let lesson = LessonRequest::builder()
+            .group_id(1)
+            .order(1)
+            .title("Math")
+            .date(date)
+            .build()
+            .unwrap();
+        let replaced = parser
+            .replace_lessons(ReplaceLessonsRequest::new(
+                1,
+                date,
+                NaiveDate::from_ymd_opt(2024, 1, 21).unwrap(),
+                vec![lesson],
+            ))
+            .await
+            .unwrap();
+
+        delete.assert_async().await;
+        replace.assert_async().await;
+        assert_eq!(deleted.deleted, 1);
+        assert_eq!(replaced.added, 0);
     }
 }
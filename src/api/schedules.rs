@@ -48,6 +48,8 @@ impl<'a> ScheduleQuery<'a> {
     }
 
     pub async fn send(self) -> Result<Vec<Schedule>> {
+        self.validate()?;
+
         let mut params = Vec::new();
 
         if let Some(date) = self.date {
@@ -90,46 +92,6 @@ impl<'a> ScheduleQuery<'a> {
 
         Ok(())
     }
-
-    fn add_query_params(self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
-        let mut request = request;
-
-        if let Some(date) = self.date {
-            request = request.query(&[("date", date)]);
-        }
-
-        if let Some(week) = self.week {
-            let week_str = match week {
-                Week::Previous => "previous",
-                Week::Current => "current",
-                Week::Next => "next",
-            };
-            request = request.query(&[("week", week_str)]);
-        }
-
-        if let Some(weekday) = self.weekday {
-            let weekday_str = match weekday {
-                Weekday::Monday => "monday",
-                Weekday::Tuesday => "tuesday",
-                Weekday::Wednesday => "wednesday",
-                Weekday::Thursday => "thursday",
-                Weekday::Friday => "friday",
-                Weekday::Saturday => "saturday",
-                Weekday::Sunday => "sunday",
-            };
-            request = request.query(&[("weekday", weekday_str)]);
-        }
-
-        if let Some(day) = self.day {
-            let day_str = match day {
-                Day::Today => "today",
-                Day::Tomorrow => "tomorrow",
-            };
-            request = request.query(&[("day", day_str)]);
-        }
-
-        request
-    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,380 @@
+use super::upload::{ChunkResult, UploadSummary};
+use crate::error::{Error, Result};
+use crate::models::validation::DATE_FORMAT;
+use crate::{LessonRequest, ParserApi, ReplaceLessonsRequest, UpdateLessonsRequest, Validate};
+use chrono::NaiveDate;
+use futures::{StreamExt, TryStreamExt};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+#[cfg(feature = "logging")]
+use tracing::debug;
+
+/// A lesson whose title, teacher or cabinet differ from the server's copy.
+#[derive(Debug, Clone, Serialize)]
+pub struct LessonChange {
+    pub before: LessonRequest,
+    pub after: LessonRequest,
+}
+
+/// Difference between the desired lessons and what the server has.
+///
+/// Only groups and dates present in the desired set are compared.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SyncPlan {
+    pub added: Vec<LessonRequest>,
+    pub changed: Vec<LessonChange>,
+    /// Lessons on the server that are missing from the desired set
+    pub removed: Vec<LessonRequest>,
+    pub unchanged: usize,
+    /// Desired lessons of every group and date with at least one difference
+    pub upload: Vec<LessonRequest>,
+}
+
+impl SyncPlan {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.changed.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Result of [`LessonSync::send`].
+#[derive(Debug)]
+pub struct SyncOutcome {
+    pub plan: SyncPlan,
+    /// One chunk per replaced day, with ranges into [`SyncPlan::upload`]
+    pub upload: UploadSummary,
+}
+
+/// Replaces only the days whose lessons differ from the server.
+///
+/// Current schedules are fetched with one request per group and date. Every
+/// day with a difference is sent through
+/// [`replace_lessons`](ParserApi::replace_lessons), so unchanged lessons are
+/// not duplicated and removed lessons are deleted from the server.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(lessons: Vec<osars::LessonRequest>) -> osars::Result<()> {
+/// use osars::Client;
+///
+/// let client = Client::new("https://api.thisishyum.ru/schedule_api/tyumen");
+/// let parser = client.authenticated().with_token("token").parser();
+///
+/// let plan = parser.sync_lessons(lessons).dry_run().await?;
+/// println!(
+///     "{} added, {} changed, {} removed",
+///     plan.added.len(),
+///     plan.changed.len(),
+///     plan.removed.len()
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub struct LessonSync<'a> {
+    api: &'a ParserApi,
+    lessons: Vec<LessonRequest>,
+    concurrency: usize,
+}
+
+type DayKey = (u32, NaiveDate);
+
+impl<'a> LessonSync<'a> {
+    pub fn new(api: &'a ParserApi, lessons: Vec<LessonRequest>) -> Self {
+        Self {
+            api,
+            lessons,
+            concurrency: 4,
+        }
+    }
+
+    /// Sets how many schedules are fetched or replaced at once
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Compares with the server without uploading anything
+    pub async fn dry_run(&self) -> Result<SyncPlan> {
+        let request = UpdateLessonsRequest {
+            lessons: self.lessons.clone(),
+        };
+        request.validate()?;
+
+        let mut desired: BTreeMap<DayKey, Vec<LessonRequest>> = BTreeMap::new();
+        for lesson in request.lessons {
+            let date = NaiveDate::parse_from_str(&lesson.date, DATE_FORMAT)
+                .map_err(|e| Error::Validation(e.to_string()))?;
+            desired
+                .entry((lesson.group_id, date))
+                .or_default()
+                .push(lesson);
+        }
+
+        let client = &self.api.client.client;
+        let current: BTreeMap<DayKey, Vec<LessonRequest>> =
+            futures::stream::iter(desired.keys().copied())
+                .map(|(group_id, date)| async move {
+                    let schedules = client
+                        .schedule(group_id)
+                        .date(&date.format(DATE_FORMAT).to_string())
+                        .send()
+                        .await?;
+                    let lessons = schedules
+                        .iter()
+                        .filter(|s| s.group_id == group_id && s.date == date)
+                        .flat_map(|s| s.lessons.iter().map(|l| LessonRequest::from_lesson(s, l)))
+                        .collect();
+                    Ok::<_, Error>(((group_id, date), lessons))
+                })
+                .buffer_unordered(self.concurrency)
+                .try_collect()
+                .await?;
+
+        let mut plan = SyncPlan::default();
+        for (key, wanted) in desired {
+            let existing = current.get(&key).map(Vec::as_slice).unwrap_or_default();
+            if diff_day(existing, &wanted, &mut plan) {
+                plan.upload.extend(wanted);
+            }
+        }
+
+        #[cfg(feature = "logging")]
+        debug!(
+            "Sync plan: {} added, {} changed, {} removed, {} unchanged",
+            plan.added.len(),
+            plan.changed.len(),
+            plan.removed.len(),
+            plan.unchanged
+        );
+
+        Ok(plan)
+    }
+
+    /// Compares with the server and replaces the days that differ
+    pub async fn send(self) -> Result<SyncOutcome> {
+        let plan = self.dry_run().await?;
+        let api = self.api;
+        let lessons = &plan.upload;
+
+        let mut chunks: Vec<ChunkResult> =
+            futures::stream::iter(day_ranges(lessons).into_iter().enumerate())
+                .map(|(index, range)| async move {
+                    let day = &lessons[range.clone()];
+                    let request = ReplaceLessonsRequest {
+                        group_id: day[0].group_id,
+                        from: day[0].date.clone(),
+                        to: day[0].date.clone(),
+                        lessons: day.to_vec(),
+                    };
+                    let result = api.replace_lessons(request).await.map(|_| ());
+                    ChunkResult {
+                        index,
+                        range,
+                        attempts: 1,
+                        result,
+                    }
+                })
+                .buffer_unordered(self.concurrency)
+                .collect()
+                .await;
+        chunks.sort_by_key(|c| c.index);
+
+        Ok(SyncOutcome {
+            upload: UploadSummary { chunks },
+            plan,
+        })
+    }
+}
+
+/// Ranges of consecutive lessons sharing a group and date
+fn day_ranges(lessons: &[LessonRequest]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for (i, lesson) in lessons.iter().enumerate() {
+        match ranges.last_mut() {
+            Some(range)
+                if lessons[range.start].group_id == lesson.group_id
+                    && lessons[range.start].date == lesson.date =>
+            {
+                range.end = i + 1
+            }
+            _ => ranges.push(i..i + 1),
+        }
+    }
+    ranges
+}
+
+fn same_content(a: &LessonRequest, b: &LessonRequest) -> bool {
+    a.title == b.title && a.teacher == b.teacher && a.cabinet == b.cabinet
+}
+
+/// Records the differences of one day in `plan`, returning whether there were any
+fn diff_day(existing: &[LessonRequest], wanted: &[LessonRequest], plan: &mut SyncPlan) -> bool {
+    let orders: BTreeSet<u32> = existing.iter().chain(wanted).map(|l| l.order).collect();
+    let mut differs = false;
+
+    for order in orders {
+        let mut before: Vec<&LessonRequest> =
+            existing.iter().filter(|l| l.order == order).collect();
+        let mut after = Vec::new();
+
+        for lesson in wanted.iter().filter(|l| l.order == order) {
+            match before.iter().position(|b| same_content(b, lesson)) {
+                Some(i) => {
+                    before.remove(i);
+                    plan.unchanged += 1;
+                }
+                None => after.push(lesson),
+            }
+        }
+
+        differs |= !before.is_empty() || !after.is_empty();
+        let paired = before.len().min(after.len());
+        for (b, a) in before.iter().zip(&after) {
+            plan.changed.push(LessonChange {
+                before: (*b).clone(),
+                after: (*a).clone(),
+            });
+        }
+        plan.removed
+            .extend(before[paired..].iter().map(|l| (*l).clone()));
+        plan.added
+            .extend(after[paired..].iter().map(|l| (*l).clone()));
+    }
+
+    differs
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Client, LessonRequest};
+    use chrono::NaiveDate;
+    use mockito::{Matcher, Server};
+
+    fn lesson(group_id: u32, day: u32, order: u32, title: &str) -> LessonRequest {
+        LessonRequest::builder()
+            .group_id(group_id)
+            .order(order)
+            .title(title)
+            .teacher("Smith")
+            .cabinet("101")
+            .date(NaiveDate::from_ymd_opt(2024, 1, day).unwrap())
+            .build()
+            .unwrap()
+    }
+
+    fn server_day(date: &str, titles: &[(u32, &str)]) -> String {
+        let lessons: Vec<String> = titles
+            .iter()
+            .map(|(order, title)| {
+                format!(
+                    r#"{{"title":"{}","cabinet":"101","teacher":"Smith","order":{},"startTime":"09:00:00","endTime":"10:30:00"}}"#,
+                    title, order
+                )
+            })
+            .collect();
+        format!(
+            r#"[{{"groupId":1,"date":"{}","lessons":[{}]}}]"#,
+            date,
+            lessons.join(",")
+        )
+    }
+
+    #[tokio::test]
+    async fn test_sync_replaces_only_changed_days() {
+        let mut server = Server::new_async().await;
+        let monday = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-01-15".into()))
+            .with_body(server_day("2024-01-15", &[(1, "Math"), (2, "Physics")]))
+            .create_async()
+            .await;
+        let tuesday = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-01-16".into()))
+            .with_body(server_day("2024-01-16", &[(1, "Math"), (3, "Art")]))
+            .create_async()
+            .await;
+        let added = server
+            .mock("POST", "/parser/lessons")
+            .expect(0)
+            .create_async()
+            .await;
+        let replace = server
+            .mock("POST", "/parser/lessons/replace")
+            .match_body(Matcher::Json(serde_json::json!({
+                "groupId": 1,
+                "from": "2024-01-16",
+                "to": "2024-01-16",
+                "lessons": [
+                    {"groupId": 1, "order": 1, "title": "Math", "teacher": "Smith", "cabinet": "101", "date": "2024-01-16"},
+                    {"groupId": 1, "order": 2, "title": "History", "teacher": "Smith", "cabinet": "101", "date": "2024-01-16"},
+                    {"groupId": 1, "order": 4, "title": "Chemistry", "teacher": "Smith", "cabinet": "101", "date": "2024-01-16"}
+                ]
+            })))
+            .with_body(r#"{"deleted":2}"#)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        let parser = client.authenticated().with_token("token").parser();
+        let desired = vec![
+            lesson(1, 15, 1, "Math"),
+            lesson(1, 15, 2, "Physics"),
+            lesson(1, 16, 1, "Math"),
+            lesson(1, 16, 2, "History"),
+            lesson(1, 16, 4, "Chemistry"),
+        ];
+
+        let outcome = parser.sync_lessons(desired).send().await.unwrap();
+
+        monday.assert_async().await;
+        tuesday.assert_async().await;
+        added.assert_async().await;
+        replace.assert_async().await;
+
+        let plan = outcome.plan;
+        assert_eq!(plan.unchanged, 3);
+        assert_eq!(plan.added.len(), 2);
+        assert!(plan.changed.is_empty());
+        assert_eq!(plan.removed.len(), 1);
+        assert_eq!(plan.removed[0].title, "Art");
+        assert_eq!(plan.upload.len(), 3);
+        assert!(outcome.upload.is_success());
+        assert_eq!(outcome.upload.chunks.len(), 1);
+        assert_eq!(outcome.upload.chunks[0].range, 0..3);
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_changes() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-01-15".into()))
+            .with_body(server_day("2024-01-15", &[(1, "Math")]))
+            .create_async()
+            .await;
+        let upload = server
+            .mock("POST", "/parser/lessons")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        let parser = client.authenticated().with_token("token").parser();
+
+        let plan = parser
+            .sync_lessons(vec![lesson(1, 15, 1, "Algebra")])
+            .dry_run()
+            .await
+            .unwrap();
+
+        upload.assert_async().await;
+        assert_eq!(plan.changed.len(), 1);
+        assert_eq!(plan.changed[0].before.title, "Math");
+        assert_eq!(plan.changed[0].after.title, "Algebra");
+        assert!(!plan.is_empty());
+    }
+}
@@ -0,0 +1,375 @@
+use crate::error::{Error, Result};
+use crate::{LessonRequest, ParserApi, UpdateLessonsRequest, Validate};
+use futures::StreamExt;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "logging")]
+use tracing::{debug, warn};
+
+/// Progress of a [`LessonUpload`], reported after every finished chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UploadProgress {
+    pub finished_chunks: usize,
+    pub total_chunks: usize,
+    /// Lessons in chunks that were uploaded successfully
+    pub uploaded_lessons: usize,
+    pub total_lessons: usize,
+}
+
+/// Outcome of uploading one chunk.
+#[derive(Debug)]
+pub struct ChunkResult {
+    pub index: usize,
+    /// Positions of the chunk's lessons in the original list
+    pub range: Range<usize>,
+    /// Number of requests made, including retries
+    pub attempts: u32,
+    pub result: Result<()>,
+}
+
+impl ChunkResult {
+    pub fn is_ok(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+/// Per-chunk results of a [`LessonUpload`], ordered by chunk index.
+#[derive(Debug, Default)]
+pub struct UploadSummary {
+    pub chunks: Vec<ChunkResult>,
+}
+
+impl UploadSummary {
+    pub fn is_success(&self) -> bool {
+        self.chunks.iter().all(ChunkResult::is_ok)
+    }
+
+    pub fn uploaded_lessons(&self) -> usize {
+        self.chunks
+            .iter()
+            .filter(|c| c.is_ok())
+            .map(|c| c.range.len())
+            .sum()
+    }
+
+    pub fn failed(&self) -> impl Iterator<Item = &ChunkResult> {
+        self.chunks.iter().filter(|c| !c.is_ok())
+    }
+}
+
+type ProgressCallback = Arc<dyn Fn(UploadProgress) + Send + Sync>;
+
+/// Uploads a large list of lessons in chunks with bounded concurrency.
+///
+/// The whole list is validated once up front; chunks that fail before the
+/// server could have stored them are retried with exponential backoff, see
+/// [`retries`](Self::retries).
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(lessons: Vec<osars::LessonRequest>) -> osars::Result<()> {
+/// use osars::Client;
+///
+/// let client = Client::new("https://api.thisishyum.ru/schedule_api/tyumen");
+/// let parser = client.authenticated().with_token("token").parser();
+///
+/// let summary = parser
+///     .upload_lessons(lessons)
+///     .chunk_size(1000)
+///     .concurrency(4)
+///     .on_progress(|p| println!("{}/{} chunks", p.finished_chunks, p.total_chunks))
+///     .send()
+///     .await?;
+/// println!("{} lessons uploaded", summary.uploaded_lessons());
+/// # Ok(())
+/// # }
+/// ```
+pub struct LessonUpload<'a> {
+    api: &'a ParserApi,
+    lessons: Vec<LessonRequest>,
+    chunk_size: usize,
+    concurrency: usize,
+    retries: u32,
+    retry_delay: Duration,
+    on_progress: Option<ProgressCallback>,
+}
+
+impl fmt::Debug for LessonUpload<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LessonUpload")
+            .field("lessons", &self.lessons.len())
+            .field("chunk_size", &self.chunk_size)
+            .field("concurrency", &self.concurrency)
+            .field("retries", &self.retries)
+            .field("retry_delay", &self.retry_delay)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> LessonUpload<'a> {
+    pub fn new(api: &'a ParserApi, lessons: Vec<LessonRequest>) -> Self {
+        Self {
+            api,
+            lessons,
+            chunk_size: 500,
+            concurrency: 4,
+            retries: 3,
+            retry_delay: Duration::from_secs(1),
+            on_progress: None,
+        }
+    }
+
+    /// Sets the number of lessons per request
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sets how many chunks may be in flight at once
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets how many times a failed chunk is retried.
+    ///
+    /// Uploading lessons is not idempotent, so only failures where the chunk
+    /// cannot have been stored are retried: connection errors, `429 Too Many
+    /// Requests` and `503 Service Unavailable`. Timeouts and other server
+    /// errors are reported in the summary instead, as retrying them could
+    /// upload the lessons twice.
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry; later retries double it
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    /// Sets a callback invoked after each chunk finishes
+    pub fn on_progress<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(UploadProgress) + Send + Sync + 'static,
+    {
+        self.on_progress = Some(Arc::new(callback));
+        self
+    }
+
+    /// Uploads all chunks.
+    ///
+    /// Fails only if the lessons do not pass validation; upload errors are
+    /// reported per chunk in the summary.
+    pub async fn send(self) -> Result<UploadSummary> {
+        let request = UpdateLessonsRequest {
+            lessons: self.lessons,
+        };
+        request.validate()?;
+
+        let total_lessons = request.lessons.len();
+        let ranges: Vec<Range<usize>> = (0..total_lessons)
+            .step_by(self.chunk_size)
+            .map(|start| start..(start + self.chunk_size).min(total_lessons))
+            .collect();
+        let total_chunks = ranges.len();
+
+        let api = self.api;
+        let lessons = &request.lessons;
+        let (retries, retry_delay) = (self.retries, self.retry_delay);
+
+        let mut results = futures::stream::iter(ranges.into_iter().enumerate())
+            .map(|(index, range)| async move {
+                let chunk = UpdateLessonsRequest {
+                    lessons: lessons[range.clone()].to_vec(),
+                };
+                let mut delay = retry_delay;
+                let mut attempts = 0;
+
+                let result = loop {
+                    attempts += 1;
+                    match api.send_lessons(&chunk).await {
+                        Ok(()) => break Ok(()),
+                        Err(e) if attempts <= retries && safe_to_retry(&e) => {
+                            #[cfg(feature = "logging")]
+                            warn!("Lesson chunk {} failed, retrying: {}", index, e);
+                            tokio::time::sleep(delay).await;
+                            delay = delay.saturating_mul(2);
+                        }
+                        Err(e) => break Err(e),
+                    }
+                };
+
+                ChunkResult {
+                    index,
+                    range,
+                    attempts,
+                    result,
+                }
+            })
+            .buffer_unordered(self.concurrency);
+
+        let mut summary = UploadSummary::default();
+        let mut uploaded_lessons = 0;
+
+        while let Some(chunk) = results.next().await {
+            #[cfg(feature = "logging")]
+            debug!(
+                "Lesson chunk {} finished after {} attempt(s)",
+                chunk.index, chunk.attempts
+            );
+
+            if chunk.is_ok() {
+                uploaded_lessons += chunk.range.len();
+            }
+            summary.chunks.push(chunk);
+
+            if let Some(callback) = &self.on_progress {
+                callback(UploadProgress {
+                    finished_chunks: summary.chunks.len(),
+                    total_chunks,
+                    uploaded_lessons,
+                    total_lessons,
+                });
+            }
+        }
+
+        summary.chunks.sort_by_key(|c| c.index);
+        Ok(summary)
+    }
+}
+
+/// Whether the server certainly did not store the chunk
+fn safe_to_retry(error: &Error) -> bool {
+    match error {
+        Error::Reqwest(e) => e.is_connect(),
+        Error::Api { status_code, .. } => matches!(status_code, 429 | 503),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Client, LessonRequest};
+    use chrono::NaiveDate;
+    use mockito::{Matcher, Server};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    fn lessons() -> Vec<LessonRequest> {
+        ["Math", "Physics", "Broken", "History", "Art"]
+            .iter()
+            .enumerate()
+            .map(|(i, title)| {
+                LessonRequest::builder()
+                    .group_id(1)
+                    .order(i as u32 + 1)
+                    .title(title)
+                    .date(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap())
+                    .build()
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_chunks_retries_and_progress() {
+        let mut server = Server::new_async().await;
+        let broken = server
+            .mock("POST", "/parser/lessons")
+            .match_body(Matcher::Regex("Broken".to_string()))
+            .with_status(503)
+            .expect(3)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("POST", "/parser/lessons")
+            .with_status(200)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        let parser = client.authenticated().with_token("token").parser();
+        let progress = Arc::new(Mutex::new(Vec::new()));
+
+        let summary = {
+            let progress = progress.clone();
+            parser
+                .upload_lessons(lessons())
+                .chunk_size(2)
+                .concurrency(2)
+                .retries(2)
+                .retry_delay(Duration::from_millis(1))
+                .on_progress(move |p| progress.lock().unwrap().push(p))
+                .send()
+                .await
+                .unwrap()
+        };
+
+        broken.assert_async().await;
+        ok.assert_async().await;
+
+        let progress = progress.lock().unwrap();
+        assert_eq!(progress.len(), 3);
+        let last = *progress.last().unwrap();
+        assert_eq!((last.finished_chunks, last.total_chunks), (3, 3));
+        assert_eq!((last.uploaded_lessons, last.total_lessons), (3, 5));
+
+        assert!(!summary.is_success());
+        assert_eq!(summary.uploaded_lessons(), 3);
+        let failed: Vec<_> = summary.failed().collect();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].index, 1);
+        assert_eq!(failed[0].range, 2..4);
+        assert_eq!(failed[0].attempts, 3);
+    }
+
+    #[tokio::test]
+    async fn test_internal_errors_are_not_retried() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/parser/lessons")
+            .with_status(500)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        let parser = client.authenticated().with_token("token").parser();
+        let summary = parser
+            .upload_lessons(lessons())
+            .retry_delay(Duration::from_millis(1))
+            .send()
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(summary.chunks[0].attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_lessons_are_not_sent() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/parser/lessons")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        let parser = client.authenticated().with_token("token").parser();
+        let mut lessons = lessons();
+        lessons.push(lessons[0].clone());
+
+        let result = parser.upload_lessons(lessons).send().await;
+
+        mock.assert_async().await;
+        assert!(matches!(result, Err(crate::Error::InvalidRequest(_))));
+    }
+}
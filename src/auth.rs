@@ -1,36 +1,107 @@
 use crate::AdminApi;
 use crate::ParserApi;
+use crate::dry_run::{RecordedRequest, RequestLog};
+use crate::error::Result;
+use crate::secret::{CredentialProvider, EnvCredentials, FileCredentials, SecretToken};
+use crate::token::TokenProvider;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::path::PathBuf;
 use std::sync::Arc;
 
-#[derive(Debug, Clone)]
+/// Credentials attached to private requests.
+///
+/// The token comes from a [`TokenProvider`] asked before every request.
+/// Printing an `Auth` or an [`AuthenticatedClient`] with `{:?}` does not
+/// leak it.
+#[derive(Clone, Default)]
 pub struct Auth {
-    pub token: Option<String>,
+    provider: Option<Arc<dyn TokenProvider>>,
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Auth")
+            .field("token", &self.provider.as_ref().map(|_| "[REDACTED]"))
+            .finish()
+    }
 }
 
 impl Auth {
     pub fn new(token: Option<String>) -> Self {
-        Self { token }
+        match token {
+            Some(token) => Self::default().with_secret(SecretToken::from(token)),
+            None => Self::default(),
+        }
     }
 
-    pub fn with_token(mut self, token: &str) -> Self {
-        self.token = Some(token.to_string());
+    /// Takes the token from `provider` once
+    pub fn from_provider(provider: &impl CredentialProvider) -> Result<Self> {
+        Ok(Self::default().with_secret(provider.token()?))
+    }
+
+    /// Reads the token from an environment variable, see [`SecretToken::from_env`]
+    pub fn from_env(var: &str) -> Result<Self> {
+        Self::from_provider(&EnvCredentials::new(var))
+    }
+
+    /// Reads the token from a file, see [`SecretToken::from_file`]
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self> {
+        Self::from_provider(&FileCredentials::new(path))
+    }
+
+    pub fn with_token(self, token: &str) -> Self {
+        self.with_secret(SecretToken::new(token))
+    }
+
+    pub fn with_secret(self, token: SecretToken) -> Self {
+        self.with_token_provider(token)
+    }
+
+    /// Asks `provider` for the token before every request
+    pub fn with_token_provider(mut self, provider: impl TokenProvider + 'static) -> Self {
+        self.provider = Some(Arc::new(provider));
         self
     }
 
     pub fn clear_token(mut self) -> Self {
-        self.token = None;
+        self.provider = None;
         self
     }
 
-    pub(crate) fn apply_to_request(
+    pub fn has_token(&self) -> bool {
+        self.provider.is_some()
+    }
+
+    /// Whether a rejected token may be replaced by a refreshed one
+    pub(crate) fn can_refresh(&self) -> bool {
+        self.provider.as_ref().is_some_and(|p| p.can_refresh())
+    }
+
+    /// The token the next request would carry
+    pub async fn token(&self) -> Result<Option<SecretToken>> {
+        match &self.provider {
+            Some(provider) => provider.token().await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Adds the current token to `request`, or the refreshed one after a `401`
+    pub(crate) async fn authorize(
         &self,
         request: reqwest::RequestBuilder,
-    ) -> reqwest::RequestBuilder {
-        if let Some(token) = &self.token {
-            request.bearer_auth(token)
+        refresh: bool,
+    ) -> Result<reqwest::RequestBuilder> {
+        let Some(provider) = &self.provider else {
+            return Ok(request);
+        };
+        let token = if refresh {
+            provider.refresh().await?
         } else {
-            request
-        }
+            provider.token().await?
+        };
+        Ok(request.bearer_auth(token.expose()))
     }
 }
 
@@ -38,6 +109,8 @@ impl Auth {
 pub struct AuthenticatedClient {
     pub client: crate::Client,
     pub auth: Auth,
+    /// Set in dry-run mode; mutating requests are recorded here instead of sent
+    pub request_log: Option<RequestLog>,
 }
 
 impl AuthenticatedClient {
@@ -45,7 +118,120 @@ impl AuthenticatedClient {
         Self {
             client,
             auth: Auth::new(None),
+            request_log: None,
+        }
+    }
+
+    /// Records POST, PATCH and DELETE requests instead of sending them.
+    ///
+    /// GET queries still reach the server. Mutating calls return the
+    /// response type's `Default` value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() -> osars::Result<()> {
+    /// use osars::{Client, UpdateGroupsRequest};
+    ///
+    /// let client = Client::new("https://api.thisishyum.ru/schedule_api/tyumen")
+    ///     .authenticated()
+    ///     .with_token("token")
+    ///     .dry_run();
+    ///
+    /// client
+    ///     .parser()
+    ///     .update_groups(UpdateGroupsRequest {
+    ///         campus_id: 1,
+    ///         student_group_names: vec!["ИС-24-2".to_string()],
+    ///     })
+    ///     .await?;
+    ///
+    /// let requests = client.request_log.unwrap().requests();
+    /// assert_eq!(requests[0].path, "/parser/groups");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dry_run(mut self) -> Self {
+        self.request_log = Some(RequestLog::new());
+        self
+    }
+
+    /// Whether mutating requests are recorded instead of sent
+    pub fn is_dry_run(&self) -> bool {
+        self.request_log.is_some()
+    }
+
+    pub(crate) async fn get<T>(&self, path: &str) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        self.client.get_json_with_auth(path, Some(&self.auth)).await
+    }
+
+    pub(crate) async fn post<T, B>(&self, path: &str, body: &B) -> Result<T>
+    where
+        T: DeserializeOwned + Default,
+        B: Serialize,
+    {
+        if let Some(log) = &self.request_log {
+            log.record(RecordedRequest {
+                method: "POST".to_string(),
+                path: path.to_string(),
+                body: Some(serde_json::to_value(body)?),
+            });
+            return Ok(T::default());
+        }
+        self.client
+            .post_json(path, Some(body), Some(&self.auth))
+            .await
+    }
+
+    pub(crate) async fn patch<T, B>(&self, path: &str, body: &B) -> Result<T>
+    where
+        T: DeserializeOwned + Default,
+        B: Serialize,
+    {
+        if let Some(log) = &self.request_log {
+            log.record(RecordedRequest {
+                method: "PATCH".to_string(),
+                path: path.to_string(),
+                body: Some(serde_json::to_value(body)?),
+            });
+            return Ok(T::default());
+        }
+        self.client.patch_json(path, body, Some(&self.auth)).await
+    }
+
+    pub(crate) async fn delete<T>(&self, path: &str) -> Result<T>
+    where
+        T: DeserializeOwned + Default,
+    {
+        self.send_delete::<T, ()>(path, None).await
+    }
+
+    pub(crate) async fn delete_with<T, B>(&self, path: &str, body: &B) -> Result<T>
+    where
+        T: DeserializeOwned + Default,
+        B: Serialize,
+    {
+        self.send_delete(path, Some(body)).await
+    }
+
+    async fn send_delete<T, B>(&self, path: &str, body: Option<&B>) -> Result<T>
+    where
+        T: DeserializeOwned + Default,
+        B: Serialize,
+    {
+        if let Some(log) = &self.request_log {
+            log.record(RecordedRequest {
+                method: "DELETE".to_string(),
+                path: path.to_string(),
+                body: body.map(serde_json::to_value).transpose()?,
+            });
+            return Ok(T::default());
         }
+        self.client.delete_json(path, body, Some(&self.auth)).await
     }
 
     pub fn with_token(mut self, token: &str) -> Self {
@@ -53,6 +239,35 @@ impl AuthenticatedClient {
         self
     }
 
+    pub fn with_secret(mut self, token: SecretToken) -> Self {
+        self.auth = self.auth.with_secret(token);
+        self
+    }
+
+    /// Takes the token from `provider`, e.g. [`EnvCredentials`] or [`FileCredentials`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use osars::{Client, EnvCredentials};
+    ///
+    /// let parser = Client::new("https://api.thisishyum.ru/schedule_api/tyumen")
+    ///     .authenticated()
+    ///     .with_credentials(&EnvCredentials::new("OSARS_TOKEN"))?
+    ///     .parser();
+    /// # Ok::<(), osars::Error>(())
+    /// ```
+    pub fn with_credentials(mut self, provider: &impl CredentialProvider) -> Result<Self> {
+        self.auth = self.auth.with_secret(provider.token()?);
+        Ok(self)
+    }
+
+    /// Asks `provider` for the token before every request, see [`TokenProvider`]
+    pub fn with_token_provider(mut self, provider: impl TokenProvider + 'static) -> Self {
+        self.auth = self.auth.with_token_provider(provider);
+        self
+    }
+
     pub fn admin(&self) -> AdminApi {
         AdminApi::new(self.clone())
     }
@@ -0,0 +1,310 @@
+use crate::favorites::Favorites;
+use chrono::{NaiveDate, NaiveDateTime};
+use osars::models::Week;
+use osars::{Campus, College, Group, Lesson, Schedule};
+use ratatui::crossterm::event::KeyCode;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Screen {
+    Colleges,
+    Campuses,
+    Groups,
+    Favorites,
+    Week,
+}
+
+/// Data the event loop has to fetch in response to a key press.
+#[derive(Debug, Clone)]
+pub enum Load {
+    Campuses(u32),
+    Groups(u32),
+    Week(u32, Week),
+}
+
+pub struct App {
+    pub screen: Screen,
+    pub selected: usize,
+    pub colleges: Vec<College>,
+    pub campuses: Vec<Campus>,
+    pub groups: Vec<Group>,
+    pub schedules: Vec<Schedule>,
+    pub week: Week,
+    pub group: Option<(u32, String)>,
+    pub favorites: Favorites,
+    pub status: Option<String>,
+    pub quit: bool,
+    history: Vec<(Screen, usize)>,
+}
+
+impl App {
+    pub fn new(colleges: Vec<College>, favorites: Favorites) -> Self {
+        Self {
+            screen: Screen::Colleges,
+            selected: 0,
+            colleges,
+            campuses: Vec::new(),
+            groups: Vec::new(),
+            schedules: Vec::new(),
+            week: Week::Current,
+            group: None,
+            favorites,
+            status: None,
+            quit: false,
+            history: Vec::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self.screen {
+            Screen::Colleges => self.colleges.len(),
+            Screen::Campuses => self.campuses.len(),
+            Screen::Groups => self.groups.len(),
+            Screen::Favorites => self.favorites.groups.len(),
+            Screen::Week => 0,
+        }
+    }
+
+    /// Handles a key press, returning data that has to be loaded next
+    pub fn on_key(&mut self, code: KeyCode) -> Option<Load> {
+        self.status = None;
+
+        match code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.selected = (self.selected + 1).min(self.len().saturating_sub(1))
+            }
+            KeyCode::Esc | KeyCode::Backspace => self.back(),
+            KeyCode::Char('F') if self.screen != Screen::Favorites => self.enter(Screen::Favorites),
+            KeyCode::Char('f') => self.toggle_favorite(),
+            KeyCode::Enter => return self.select(),
+            KeyCode::Left | KeyCode::Char('h') if self.screen == Screen::Week => {
+                return self.shift_week(false);
+            }
+            KeyCode::Right | KeyCode::Char('l') if self.screen == Screen::Week => {
+                return self.shift_week(true);
+            }
+            _ => {}
+        }
+
+        None
+    }
+
+    fn select(&mut self) -> Option<Load> {
+        match self.screen {
+            Screen::Colleges => self
+                .colleges
+                .get(self.selected)
+                .map(|c| Load::Campuses(c.college_id)),
+            Screen::Campuses => self.campuses.get(self.selected).map(|c| Load::Groups(c.id)),
+            Screen::Groups => {
+                let group = self.groups.get(self.selected)?;
+                self.group = Some((group.id, group.name.clone()));
+                Some(Load::Week(group.id, Week::Current))
+            }
+            Screen::Favorites => {
+                let favorite = self.favorites.groups.get(self.selected)?;
+                self.group = Some((favorite.group_id, favorite.name.clone()));
+                Some(Load::Week(favorite.group_id, Week::Current))
+            }
+            Screen::Week => None,
+        }
+    }
+
+    fn shift_week(&mut self, forward: bool) -> Option<Load> {
+        let week = match (&self.week, forward) {
+            (Week::Previous, true) => Week::Current,
+            (Week::Current, true) => Week::Next,
+            (Week::Current, false) => Week::Previous,
+            (Week::Next, false) => Week::Current,
+            _ => return None,
+        };
+        let (group_id, _) = self.group.as_ref()?;
+        Some(Load::Week(*group_id, week))
+    }
+
+    fn toggle_favorite(&mut self) {
+        let group = match self.screen {
+            Screen::Groups => self
+                .groups
+                .get(self.selected)
+                .map(|g| (g.id, g.name.clone())),
+            Screen::Week => self.group.clone(),
+            _ => None,
+        };
+
+        if let Some((id, name)) = group
+            && let Err(e) = self.favorites.toggle(id, &name)
+        {
+            self.status = Some(format!("Failed to save favourites: {}", e));
+        }
+    }
+
+    fn enter(&mut self, screen: Screen) {
+        if self.screen != screen {
+            self.history.push((self.screen, self.selected));
+            self.screen = screen;
+            self.selected = 0;
+        }
+    }
+
+    fn back(&mut self) {
+        match self.history.pop() {
+            Some((screen, selected)) => {
+                self.screen = screen;
+                self.selected = selected;
+            }
+            None => self.quit = true,
+        }
+    }
+
+    pub fn show_campuses(&mut self, campuses: Vec<Campus>) {
+        self.campuses = campuses;
+        self.enter(Screen::Campuses);
+    }
+
+    pub fn show_groups(&mut self, groups: Vec<Group>) {
+        self.groups = groups;
+        self.enter(Screen::Groups);
+    }
+
+    pub fn show_week(&mut self, week: Week, schedules: Vec<Schedule>) {
+        self.week = week;
+        self.schedules = schedules;
+        self.enter(Screen::Week);
+    }
+}
+
+/// Lessons of a week laid out by date (columns) and order (rows).
+#[derive(Debug, Default)]
+pub struct WeekGrid {
+    pub dates: Vec<NaiveDate>,
+    pub orders: Vec<u32>,
+    pub cells: BTreeMap<(NaiveDate, u32), Vec<Lesson>>,
+}
+
+impl WeekGrid {
+    pub fn new(schedules: &[Schedule]) -> Self {
+        let mut dates = BTreeSet::new();
+        let mut orders = BTreeSet::new();
+        let mut cells: BTreeMap<_, Vec<Lesson>> = BTreeMap::new();
+
+        for schedule in schedules {
+            dates.insert(schedule.date);
+            for lesson in &schedule.lessons {
+                orders.insert(lesson.order);
+                cells
+                    .entry((schedule.date, lesson.order))
+                    .or_default()
+                    .push(lesson.clone());
+            }
+        }
+
+        Self {
+            dates: dates.into_iter().collect(),
+            orders: orders.into_iter().collect(),
+            cells,
+        }
+    }
+}
+
+/// Whether `lesson` on `date` is in progress at `now`
+pub fn is_current(date: NaiveDate, lesson: &Lesson, now: NaiveDateTime) -> bool {
+    now.date() == date && lesson.start_time <= now.time() && now.time() < lesson.end_time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn lesson(order: u32) -> Lesson {
+        Lesson {
+            title: "Math".to_string(),
+            cabinet: "101".to_string(),
+            teacher: "Smith".to_string(),
+            order,
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+        }
+    }
+
+    fn college(id: u32) -> College {
+        College {
+            college_id: id,
+            name: format!("College {}", id),
+            calls: vec![],
+            campuses: vec![],
+        }
+    }
+
+    #[test]
+    fn test_drill_down_and_back() {
+        let mut app = App::new(vec![college(1), college(2)], Favorites::default());
+
+        app.on_key(KeyCode::Down);
+        assert!(matches!(
+            app.on_key(KeyCode::Enter),
+            Some(Load::Campuses(2))
+        ));
+
+        app.show_campuses(vec![Campus {
+            id: 7,
+            name: "Main".to_string(),
+            college_id: 2,
+            groups: vec![],
+        }]);
+        assert_eq!(app.screen, Screen::Campuses);
+        assert_eq!(app.selected, 0);
+
+        app.on_key(KeyCode::Esc);
+        assert_eq!(app.screen, Screen::Colleges);
+        assert_eq!(app.selected, 1);
+    }
+
+    #[test]
+    fn test_week_navigation() {
+        let mut app = App::new(vec![], Favorites::default());
+        app.group = Some((3, "ИС-24-2".to_string()));
+        app.show_week(Week::Current, vec![]);
+
+        assert!(matches!(
+            app.on_key(KeyCode::Right),
+            Some(Load::Week(3, Week::Next))
+        ));
+        app.show_week(Week::Next, vec![]);
+        assert!(app.on_key(KeyCode::Right).is_none());
+        assert!(matches!(
+            app.on_key(KeyCode::Left),
+            Some(Load::Week(3, Week::Current))
+        ));
+    }
+
+    #[test]
+    fn test_week_grid_and_current_lesson() {
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let tuesday = monday.succ_opt().unwrap();
+        let schedules = vec![
+            Schedule {
+                group_id: 1,
+                date: tuesday,
+                lessons: vec![lesson(3)],
+            },
+            Schedule {
+                group_id: 1,
+                date: monday,
+                lessons: vec![lesson(1)],
+            },
+        ];
+
+        let grid = WeekGrid::new(&schedules);
+        assert_eq!(grid.dates, vec![monday, tuesday]);
+        assert_eq!(grid.orders, vec![1, 3]);
+        assert!(!grid.cells.contains_key(&(monday, 3)));
+
+        let now = monday.and_hms_opt(9, 45, 0).unwrap();
+        assert!(is_current(monday, &lesson(1), now));
+        assert!(!is_current(tuesday, &lesson(3), now));
+    }
+}
@@ -0,0 +1,86 @@
+use osars::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A group pinned by the user for quick access.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Favorite {
+    pub group_id: u32,
+    pub name: String,
+}
+
+/// Favourite groups persisted as JSON between sessions.
+#[derive(Debug, Default)]
+pub struct Favorites {
+    path: Option<PathBuf>,
+    pub groups: Vec<Favorite>,
+}
+
+impl Favorites {
+    /// Default location of the favourites file
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("osars").join("favorites.json"))
+    }
+
+    /// Loads favourites, treating a missing file as empty
+    pub fn load(path: &Path) -> Result<Self> {
+        let groups = match std::fs::read_to_string(path) {
+            Ok(raw) => serde_json::from_str(&raw)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path: Some(path.to_path_buf()),
+            groups,
+        })
+    }
+
+    pub fn contains(&self, group_id: u32) -> bool {
+        self.groups.iter().any(|f| f.group_id == group_id)
+    }
+
+    /// Adds or removes a group and writes the file
+    pub fn toggle(&mut self, group_id: u32, name: &str) -> Result<()> {
+        if self.contains(group_id) {
+            self.groups.retain(|f| f.group_id != group_id);
+        } else {
+            self.groups.push(Favorite {
+                group_id,
+                name: name.to_string(),
+            });
+        }
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.groups)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_persists() {
+        let path =
+            std::env::temp_dir().join(format!("osars-favorites-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut favorites = Favorites::load(&path).unwrap();
+        favorites.toggle(5, "ИС-24-2").unwrap();
+        assert!(Favorites::load(&path).unwrap().contains(5));
+
+        favorites.toggle(5, "ИС-24-2").unwrap();
+        assert!(!Favorites::load(&path).unwrap().contains(5));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,87 @@
+mod app;
+mod favorites;
+mod ui;
+
+use app::{App, Load};
+use chrono::Local;
+use favorites::Favorites;
+use osars::{Client, Result};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyEventKind};
+use std::time::Duration;
+
+const DEFAULT_BASE_URL: &str = "https://api.thisishyum.ru/schedule_api/tyumen";
+
+#[tokio::main]
+async fn main() {
+    let base_url = std::env::args()
+        .nth(1)
+        .or_else(|| std::env::var("OSARS_BASE_URL").ok())
+        .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+    let client = Client::new(&base_url);
+
+    let mut terminal = ratatui::init();
+    let result = run(&mut terminal, &client).await;
+    ratatui::restore();
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(terminal: &mut DefaultTerminal, client: &Client) -> Result<()> {
+    let favorites = match Favorites::default_path() {
+        Some(path) => Favorites::load(&path)?,
+        None => Favorites::default(),
+    };
+    let colleges = client.colleges().send().await?;
+    let mut app = App::new(colleges, favorites);
+
+    while !app.quit {
+        terminal.draw(|frame| ui::draw(frame, &app, Local::now().naive_local()))?;
+
+        // Redraw periodically so the current lesson highlight follows the clock
+        if !event::poll(Duration::from_secs(30))? {
+            continue;
+        }
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        if let Some(load) = app.on_key(key.code) {
+            app.status = Some("Loading…".to_string());
+            terminal.draw(|frame| ui::draw(frame, &app, Local::now().naive_local()))?;
+
+            if let Err(e) = perform(&mut app, client, load).await {
+                app.status = Some(format!("Error: {}", e));
+            } else {
+                app.status = None;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn perform(app: &mut App, client: &Client, load: Load) -> Result<()> {
+    match load {
+        Load::Campuses(college_id) => {
+            let client = client.clone().with_college(college_id);
+            let campuses = client.campuses()?.send().await?;
+            app.show_campuses(campuses);
+        }
+        Load::Groups(campus_id) => {
+            let groups = client.groups(campus_id).send().await?;
+            app.show_groups(groups);
+        }
+        Load::Week(group_id, week) => {
+            let schedules = client.schedule(group_id).week(week).send().await?;
+            app.show_week(week, schedules);
+        }
+    }
+    Ok(())
+}
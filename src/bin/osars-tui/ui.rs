@@ -0,0 +1,142 @@
+use crate::app::{App, Screen, WeekGrid, is_current};
+use chrono::NaiveDateTime;
+use osars::locale::{English, Locale};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style, Stylize};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, Borders, Cell, List, ListItem, ListState, Paragraph, Row, Table};
+
+pub fn draw(frame: &mut Frame, app: &App, now: NaiveDateTime) {
+    let [main, footer] =
+        Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+
+    match app.screen {
+        Screen::Colleges => {
+            let items = app.colleges.iter().map(|c| c.name.clone()).collect();
+            draw_list(frame, main, "Colleges", items, app.selected);
+        }
+        Screen::Campuses => {
+            let items = app.campuses.iter().map(|c| c.name.clone()).collect();
+            draw_list(frame, main, "Campuses", items, app.selected);
+        }
+        Screen::Groups => {
+            let items = app
+                .groups
+                .iter()
+                .map(|g| {
+                    let star = if app.favorites.contains(g.id) {
+                        "★ "
+                    } else {
+                        "  "
+                    };
+                    format!("{}{}", star, g.name)
+                })
+                .collect();
+            draw_list(frame, main, "Groups", items, app.selected);
+        }
+        Screen::Favorites => {
+            let items = app
+                .favorites
+                .groups
+                .iter()
+                .map(|f| f.name.clone())
+                .collect();
+            draw_list(frame, main, "Favourite groups", items, app.selected);
+        }
+        Screen::Week => draw_week(frame, main, app, now),
+    }
+
+    let help = match app.screen {
+        Screen::Week => "←/→ week  f favourite  Esc back  q quit",
+        _ => "↑/↓ move  Enter open  f favourite  F favourites  Esc back  q quit",
+    };
+    let footer_text = app.status.as_deref().unwrap_or(help);
+    frame.render_widget(Paragraph::new(footer_text).dim(), footer);
+}
+
+fn draw_list(
+    frame: &mut Frame,
+    area: ratatui::layout::Rect,
+    title: &str,
+    items: Vec<String>,
+    selected: usize,
+) {
+    let list = List::new(items.into_iter().map(ListItem::new))
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+fn draw_week(frame: &mut Frame, area: ratatui::layout::Rect, app: &App, now: NaiveDateTime) {
+    let grid = WeekGrid::new(&app.schedules);
+    let week = English.week(&app.week);
+    let group = app
+        .group
+        .as_ref()
+        .map(|(_, name)| name.as_str())
+        .unwrap_or("");
+    let star = match &app.group {
+        Some((id, _)) if app.favorites.contains(*id) => " ★",
+        _ => "",
+    };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("{}{} — {}", group, star, week));
+
+    if grid.dates.is_empty() {
+        frame.render_widget(Paragraph::new("No lessons").block(block), area);
+        return;
+    }
+
+    let header = Row::new(
+        std::iter::once(Cell::from("#")).chain(
+            grid.dates
+                .iter()
+                .map(|d| Cell::from(d.format("%a %d.%m").to_string()).bold()),
+        ),
+    );
+
+    let rows = grid.orders.iter().map(|order| {
+        let cells = grid.dates.iter().map(|date| {
+            let lessons = grid.cells.get(&(*date, *order));
+            let Some(lessons) = lessons else {
+                return Cell::from("");
+            };
+
+            let current = lessons.iter().any(|l| is_current(*date, l, now));
+            let lines: Vec<Line> = lessons
+                .iter()
+                .flat_map(|l| {
+                    [
+                        Line::from(l.title.clone()),
+                        Line::from(format!("{} {}", l.start_time.format("%H:%M"), l.cabinet)).dim(),
+                    ]
+                })
+                .collect();
+
+            let cell = Cell::from(Text::from(lines));
+            if current {
+                cell.style(Style::default().add_modifier(Modifier::REVERSED))
+            } else {
+                cell
+            }
+        });
+
+        let height = grid
+            .dates
+            .iter()
+            .filter_map(|d| grid.cells.get(&(*d, *order)))
+            .map(|l| l.len() * 2)
+            .max()
+            .unwrap_or(1);
+
+        Row::new(std::iter::once(Cell::from(order.to_string())).chain(cells)).height(height as u16)
+    });
+
+    let widths = std::iter::once(Constraint::Length(3))
+        .chain(grid.dates.iter().map(|_| Constraint::Fill(1)));
+    let table = Table::new(rows, widths).header(header).block(block);
+    frame.render_widget(table, area);
+}
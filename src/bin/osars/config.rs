@@ -0,0 +1,84 @@
+use osars::{Error, Result, SecretToken};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+pub const DEFAULT_BASE_URL: &str = "https://api.thisishyum.ru/schedule_api/tyumen";
+
+/// Settings read from `~/.config/osars/config.toml`.
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    pub base_url: Option<String>,
+    pub token: Option<SecretToken>,
+    /// File holding the token, used when `token` is not set
+    pub token_file: Option<PathBuf>,
+}
+
+impl Config {
+    /// Default location of the config file
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("osars").join("config.toml"))
+    }
+
+    /// Loads the config file, treating a missing file as empty
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(raw) => toml::from_str(&raw).map_err(|e| {
+                Error::Validation(format!("invalid config {}: {}", path.display(), e))
+            }),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Picks the base URL from the command line or environment, then the file
+    pub fn base_url(&self, flag: Option<String>) -> String {
+        flag.or_else(|| self.base_url.clone())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+    }
+
+    /// Picks the token from the command line or environment, then the file
+    pub fn token(&self, flag: Option<String>) -> Result<SecretToken> {
+        if let Some(token) = flag.map(SecretToken::from).or_else(|| self.token.clone()) {
+            return Ok(token);
+        }
+        match &self.token_file {
+            Some(path) => SecretToken::from_file(path),
+            None => Err(Error::Validation(
+                "No token configured. Use --token or OSARS_TOKEN".into(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flag_overrides_file() {
+        let config: Config = toml::from_str(
+            r#"
+            base_url = "https://file.example.com"
+            token = "file-token"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.base_url(None), "https://file.example.com");
+        assert_eq!(
+            config.base_url(Some("https://flag.example.com".into())),
+            "https://flag.example.com"
+        );
+        assert_eq!(
+            config.token(Some("flag-token".into())).unwrap().expose(),
+            "flag-token"
+        );
+        assert_eq!(config.token(None).unwrap().expose(), "file-token");
+    }
+
+    #[test]
+    fn test_missing_token() {
+        assert!(Config::default().token(None).is_err());
+        assert_eq!(Config::default().base_url(None), DEFAULT_BASE_URL);
+    }
+}
@@ -0,0 +1,391 @@
+mod config;
+mod output;
+
+use chrono::NaiveDate;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use config::Config;
+use osars::models::Week;
+use osars::{
+    AddCampusesRequest, Client, CreateParserRequest, DeleteCallsRequest, DeleteGroupsRequest,
+    DeleteLessonsRequest, RenameCampusRequest, ReplaceLessonsRequest, Result, UpdateCallsRequest,
+    UpdateGroupsRequest, UpdateLessonsRequest,
+};
+use output::Format;
+use std::path::{Path, PathBuf};
+
+/// Command-line client for OpenScheduleAPI
+#[derive(Debug, Parser)]
+#[command(name = "osars", version, about)]
+struct Cli {
+    /// Base URL of the API instance
+    #[arg(long, env = "OSARS_BASE_URL", global = true)]
+    base_url: Option<String>,
+
+    /// Bearer token for parser and admin commands
+    #[arg(long, env = "OSARS_TOKEN", global = true, hide_env_values = true)]
+    token: Option<String>,
+
+    /// Path to the config file
+    #[arg(long, env = "OSARS_CONFIG", global = true)]
+    config: Option<PathBuf>,
+
+    /// Output format; `ics` is only supported by `schedule`
+    #[arg(long, short, value_enum, default_value = "table", global = true)]
+    format: Format,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List colleges
+    Colleges {
+        /// Filter by name
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// List campuses of a college
+    Campuses {
+        college: u32,
+        /// Filter by name
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// List groups of a campus
+    Groups {
+        campus: u32,
+        /// Filter by name
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Show the schedule of a group
+    Schedule(ScheduleArgs),
+    /// Parser endpoints (requires a parser token)
+    #[command(subcommand)]
+    Parser(ParserCommand),
+    /// Admin endpoints (requires an admin token)
+    #[command(subcommand)]
+    Admin(AdminCommand),
+}
+
+#[derive(Debug, Args)]
+struct ScheduleArgs {
+    group: u32,
+    #[command(flatten)]
+    when: When,
+}
+
+#[derive(Debug, Args)]
+#[group(multiple = false)]
+struct When {
+    /// Today's lessons
+    #[arg(long)]
+    today: bool,
+    /// Tomorrow's lessons
+    #[arg(long)]
+    tomorrow: bool,
+    /// Lessons on a date (YYYY-MM-DD)
+    #[arg(long)]
+    date: Option<String>,
+    /// Lessons of a week
+    #[arg(long, value_enum)]
+    week: Option<WeekArg>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum WeekArg {
+    Previous,
+    Current,
+    Next,
+}
+
+impl From<WeekArg> for Week {
+    fn from(week: WeekArg) -> Self {
+        match week {
+            WeekArg::Previous => Week::Previous,
+            WeekArg::Current => Week::Current,
+            WeekArg::Next => Week::Next,
+        }
+    }
+}
+
+#[derive(Debug, Subcommand)]
+enum ParserCommand {
+    /// Replace the groups of a campus
+    Groups { campus: u32, names: Vec<String> },
+    /// Upload a call timetable from a JSON file (`UpdateCallsRequest`)
+    Calls { file: PathBuf },
+    /// Upload lessons from a JSON file (`UpdateLessonsRequest`)
+    Lessons { file: PathBuf },
+    /// Replace a group's lessons from a JSON file (`ReplaceLessonsRequest`)
+    ReplaceLessons { file: PathBuf },
+    /// Delete a group's lessons between two dates (YYYY-MM-DD)
+    DeleteLessons {
+        group: u32,
+        #[arg(long)]
+        from: NaiveDate,
+        /// Defaults to `--from`
+        #[arg(long)]
+        to: Option<NaiveDate>,
+        /// Delete only the lesson with this order
+        #[arg(long)]
+        order: Option<u32>,
+    },
+    /// Remove groups from a campus
+    DeleteGroups { campus: u32, names: Vec<String> },
+    /// Remove calls by order
+    DeleteCalls { orders: Vec<u32> },
+}
+
+#[derive(Debug, Subcommand)]
+enum AdminCommand {
+    /// List all parsers
+    Parsers,
+    /// Show a parser with its campuses
+    Parser { parser_id: u32 },
+    /// Create a parser for a college
+    CreateParser {
+        #[arg(long)]
+        college: String,
+        #[arg(long = "campus")]
+        campuses: Vec<String>,
+    },
+    /// Issue a new token for a parser
+    RotateToken { parser_id: u32 },
+    /// Add campuses to a parser's college
+    AddCampuses { parser_id: u32, names: Vec<String> },
+    /// Rename a campus
+    RenameCampus { campus_id: u32, name: String },
+    /// Delete a parser
+    DeleteParser { parser_id: u32 },
+}
+
+impl Cli {
+    /// Rejects argument combinations clap cannot express
+    fn check(&self) -> std::result::Result<(), clap::Error> {
+        if self.format == Format::Ics && !matches!(self.command, Command::Schedule(_)) {
+            return Err(Cli::command().error(
+                ErrorKind::ArgumentConflict,
+                "--format ics is only supported by the schedule command",
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = cli.check() {
+        e.exit();
+    }
+    if let Err(e) = run(cli).await {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let config = match cli.config.clone().or_else(Config::default_path) {
+        Some(path) => Config::load(&path)?,
+        None => Config::default(),
+    };
+    let client = Client::new(&config.base_url(cli.base_url.clone()));
+    let format = cli.format;
+
+    match cli.command {
+        Command::Colleges { name } => {
+            let mut query = client.colleges();
+            if let Some(name) = &name {
+                query = query.name(name);
+            }
+            output::colleges(&query.send().await?, format)
+        }
+        Command::Campuses { college, name } => {
+            let client = client.with_college(college);
+            let mut query = client.campuses()?;
+            if let Some(name) = &name {
+                query = query.name(name);
+            }
+            output::campuses(&query.send().await?, format)
+        }
+        Command::Groups { campus, name } => {
+            let mut query = client.groups(campus);
+            if let Some(name) = &name {
+                query = query.name(name);
+            }
+            output::groups(&query.send().await?, format)
+        }
+        Command::Schedule(args) => {
+            let mut query = client.schedule(args.group);
+            let when = args.when;
+            if when.today {
+                query = query.today();
+            } else if when.tomorrow {
+                query = query.tomorrow();
+            } else if let Some(date) = &when.date {
+                query = query.date(date);
+            } else if let Some(week) = when.week {
+                query = query.week(week.into());
+            }
+            output::schedules(&query.send().await?, format)
+        }
+        Command::Parser(command) => {
+            let token = config.token(cli.token)?;
+            let parser = client.authenticated().with_secret(token).parser();
+            match command {
+                ParserCommand::Groups { campus, names } => {
+                    parser
+                        .update_groups(UpdateGroupsRequest {
+                            campus_id: campus,
+                            student_group_names: names,
+                        })
+                        .await
+                }
+                ParserCommand::Calls { file } => {
+                    let request: UpdateCallsRequest = read_json(&file)?;
+                    parser.update_calls(request).await
+                }
+                ParserCommand::Lessons { file } => {
+                    let request: UpdateLessonsRequest = read_json(&file)?;
+                    parser.add_lessons(request).await
+                }
+                ParserCommand::ReplaceLessons { file } => {
+                    let request: ReplaceLessonsRequest = read_json(&file)?;
+                    let response = parser.replace_lessons(request).await?;
+                    match format {
+                        Format::Json => output::print_json(&response),
+                        _ => {
+                            println!("{} deleted, {} added", response.deleted, response.added);
+                            Ok(())
+                        }
+                    }
+                }
+                ParserCommand::DeleteLessons {
+                    group,
+                    from,
+                    to,
+                    order,
+                } => {
+                    let request = DeleteLessonsRequest {
+                        order,
+                        ..DeleteLessonsRequest::range(group, from, to.unwrap_or(from))
+                    };
+                    output::deleted(parser.delete_lessons(request).await?, format)
+                }
+                ParserCommand::DeleteGroups { campus, names } => {
+                    let request = DeleteGroupsRequest {
+                        campus_id: campus,
+                        student_group_names: names,
+                    };
+                    output::deleted(parser.delete_groups(request).await?, format)
+                }
+                ParserCommand::DeleteCalls { orders } => {
+                    let request = DeleteCallsRequest { orders };
+                    output::deleted(parser.delete_calls(request).await?, format)
+                }
+            }
+        }
+        Command::Admin(command) => {
+            let token = config.token(cli.token)?;
+            let admin = client.authenticated().with_secret(token).admin();
+            match command {
+                AdminCommand::Parsers => output::parsers(&admin.parsers().await?, format),
+                AdminCommand::Parser { parser_id } => {
+                    let parser = admin.parser(parser_id).await?;
+                    match format {
+                        Format::Json => output::print_json(&parser),
+                        _ => output::campuses(&parser.campuses, format),
+                    }
+                }
+                AdminCommand::CreateParser { college, campuses } => {
+                    let response = admin
+                        .create_parser(CreateParserRequest {
+                            college_name: college,
+                            campus_names: campuses,
+                        })
+                        .await?;
+                    match format {
+                        Format::Json => output::print_json(&response),
+                        _ => {
+                            println!("{}", response.token);
+                            Ok(())
+                        }
+                    }
+                }
+                AdminCommand::RotateToken { parser_id } => {
+                    let response = admin.rotate_token(parser_id).await?;
+                    match format {
+                        Format::Json => output::print_json(&response),
+                        _ => {
+                            println!("{}", response.token);
+                            Ok(())
+                        }
+                    }
+                }
+                AdminCommand::AddCampuses { parser_id, names } => {
+                    let campuses = admin
+                        .add_campuses(
+                            parser_id,
+                            AddCampusesRequest {
+                                campus_names: names,
+                            },
+                        )
+                        .await?;
+                    output::campuses(&campuses, format)
+                }
+                AdminCommand::RenameCampus { campus_id, name } => {
+                    admin
+                        .rename_campus(campus_id, RenameCampusRequest { name })
+                        .await
+                }
+                AdminCommand::DeleteParser { parser_id } => admin.delete_parser(parser_id).await,
+            }
+        }
+    }
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T> {
+    let raw = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&raw)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_schedule_flags_conflict() {
+        let result = Cli::try_parse_from(["osars", "schedule", "1", "--today", "--tomorrow"]);
+        assert!(result.is_err());
+
+        let cli =
+            Cli::try_parse_from(["osars", "schedule", "1", "--week", "next", "-f", "ics"]).unwrap();
+        assert_eq!(cli.format, Format::Ics);
+        assert!(cli.check().is_ok());
+        assert!(matches!(
+            cli.command,
+            Command::Schedule(ScheduleArgs {
+                group: 1,
+                when: When {
+                    week: Some(WeekArg::Next),
+                    ..
+                },
+            })
+        ));
+    }
+    #[test]
+    fn test_ics_is_only_for_schedules() {
+        let cli = Cli::try_parse_from(["osars", "colleges", "-f", "ics"]).unwrap();
+        let error = cli.check().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::ArgumentConflict);
+        assert!(error.to_string().contains("schedule command"));
+    }
+}
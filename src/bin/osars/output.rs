@@ -0,0 +1,255 @@
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use osars::render::{RenderStyle, Renderer};
+use osars::{Campus, College, DeleteResponse, Group, ParserInfo, Result, Schedule};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    Table,
+    Json,
+    Ics,
+}
+
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+pub fn colleges(colleges: &[College], format: Format) -> Result<()> {
+    match format {
+        Format::Json => print_json(colleges),
+        _ => {
+            let rows = colleges
+                .iter()
+                .map(|c| vec![c.college_id.to_string(), c.name.clone()])
+                .collect();
+            print!("{}", table(&["ID", "Name"], rows));
+            Ok(())
+        }
+    }
+}
+
+pub fn campuses(campuses: &[Campus], format: Format) -> Result<()> {
+    match format {
+        Format::Json => print_json(campuses),
+        _ => {
+            let rows = campuses
+                .iter()
+                .map(|c| vec![c.id.to_string(), c.name.clone()])
+                .collect();
+            print!("{}", table(&["ID", "Name"], rows));
+            Ok(())
+        }
+    }
+}
+
+pub fn groups(groups: &[Group], format: Format) -> Result<()> {
+    match format {
+        Format::Json => print_json(groups),
+        _ => {
+            let rows = groups
+                .iter()
+                .map(|g| vec![g.id.to_string(), g.name.clone()])
+                .collect();
+            print!("{}", table(&["ID", "Name"], rows));
+            Ok(())
+        }
+    }
+}
+
+pub fn parsers(parsers: &[ParserInfo], format: Format) -> Result<()> {
+    match format {
+        Format::Json => print_json(parsers),
+        _ => {
+            let rows = parsers
+                .iter()
+                .map(|p| {
+                    let campuses: Vec<&str> = p.campuses.iter().map(|c| c.name.as_str()).collect();
+                    vec![
+                        p.id.to_string(),
+                        p.college_name.clone(),
+                        campuses.join(", "),
+                    ]
+                })
+                .collect();
+            print!("{}", table(&["ID", "College", "Campuses"], rows));
+            Ok(())
+        }
+    }
+}
+
+pub fn deleted(response: DeleteResponse, format: Format) -> Result<()> {
+    match format {
+        Format::Json => print_json(&response),
+        _ => {
+            println!("{} deleted", response.deleted);
+            Ok(())
+        }
+    }
+}
+
+pub fn schedules(schedules: &[Schedule], format: Format) -> Result<()> {
+    match format {
+        Format::Json => print_json(schedules),
+        Format::Ics => {
+            print!("{}", ics(schedules, Utc::now()));
+            Ok(())
+        }
+        Format::Table => {
+            print!("{}", Renderer::new(RenderStyle::Table).week(schedules));
+            Ok(())
+        }
+    }
+}
+
+/// Lays out rows in columns padded to the widest cell.
+pub fn table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: Vec<&str>| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect();
+        format!("{}\n", padded.join("  ").trim_end())
+    };
+
+    let mut out = line(headers.to_vec());
+    let rule: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+    out.push_str(&line(rule.iter().map(String::as_str).collect()));
+    for row in &rows {
+        out.push_str(&line(row.iter().map(String::as_str).collect()));
+    }
+    out
+}
+
+/// Renders lessons as an iCalendar document with floating local times.
+pub fn ics(schedules: &[Schedule], stamp: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//osars//schedule//EN".to_string(),
+    ];
+
+    for schedule in schedules {
+        for lesson in &schedule.lessons {
+            let date = schedule.date.format("%Y%m%d");
+            lines.extend([
+                "BEGIN:VEVENT".to_string(),
+                format!("UID:{}-{}-{}@osars", schedule.group_id, date, lesson.order),
+                format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")),
+                format!("DTSTART:{}T{}", date, lesson.start_time.format("%H%M%S")),
+                format!("DTEND:{}T{}", date, lesson.end_time.format("%H%M%S")),
+                format!("SUMMARY:{}", escape_ics(&lesson.title)),
+                format!("LOCATION:{}", escape_ics(&lesson.cabinet)),
+                format!("DESCRIPTION:{}", escape_ics(&lesson.teacher)),
+                "END:VEVENT".to_string(),
+            ]);
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+    lines.iter().map(|line| fold_ics(line)).collect()
+}
+
+/// Folds a content line into 75-octet pieces (RFC 5545, section 3.1),
+/// without splitting UTF-8 characters; ends with CRLF.
+fn fold_ics(line: &str) -> String {
+    let mut out = String::with_capacity(line.len() + 2);
+    // Continuation lines start with a space, which counts towards the limit
+    let mut budget = 75;
+    let mut used = 0;
+
+    for c in line.chars() {
+        if used + c.len_utf8() > budget {
+            out.push_str("\r\n ");
+            budget = 74;
+            used = 0;
+        }
+        out.push(c);
+        used += c.len_utf8();
+    }
+    out.push_str("\r\n");
+    out
+}
+
+fn escape_ics(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime};
+    use osars::Lesson;
+
+    #[test]
+    fn test_ics_event() {
+        let schedules = vec![Schedule {
+            group_id: 7,
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            lessons: vec![Lesson {
+                title: "Math; algebra".to_string(),
+                cabinet: "101".to_string(),
+                teacher: "Smith, J.".to_string(),
+                order: 2,
+                start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                end_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+            }],
+        }];
+        let stamp = DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+            .unwrap()
+            .to_utc();
+
+        let ics = ics(&schedules, stamp);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.contains("UID:7-20240115-2@osars\r\n"));
+        assert!(ics.contains("DTSTART:20240115T090000\r\n"));
+        assert!(ics.contains("DTEND:20240115T103000\r\n"));
+        assert!(ics.contains("SUMMARY:Math\\; algebra\r\n"));
+        assert!(ics.contains("DESCRIPTION:Smith\\, J.\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+    }
+
+    #[test]
+    fn test_ics_folds_long_lines() {
+        let line = format!("SUMMARY:{}", "Основы алгоритмизации ".repeat(5));
+        let folded = fold_ics(&line);
+
+        let pieces: Vec<&str> = folded.trim_end_matches("\r\n").split("\r\n").collect();
+        assert!(pieces.len() > 1);
+        assert!(pieces.iter().all(|piece| piece.len() <= 75));
+        assert!(pieces[1..].iter().all(|piece| piece.starts_with(' ')));
+
+        let unfolded = folded.trim_end_matches("\r\n").replace("\r\n ", "");
+        assert_eq!(unfolded, line);
+        assert_eq!(fold_ics("VERSION:2.0"), "VERSION:2.0\r\n");
+    }
+
+    #[test]
+    fn test_table_alignment() {
+        let out = table(
+            &["ID", "Name"],
+            vec![
+                vec!["1".into(), "Колледж".into()],
+                vec!["10".into(), "B".into()],
+            ],
+        );
+        let lines: Vec<_> = out.lines().collect();
+        assert_eq!(lines[0], "ID  Name");
+        assert_eq!(lines[1], "--  -------");
+        assert_eq!(lines[2], "1   Колледж");
+        assert_eq!(lines[3], "10  B");
+    }
+}
@@ -0,0 +1,377 @@
+use crate::error::{Error, Result};
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[cfg(feature = "logging")]
+use tracing::{debug, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests are sent normally
+    Closed,
+    /// Requests fail fast with [`Error::CircuitOpen`]
+    Open,
+    /// One probe request is let through to test the API
+    HalfOpen,
+}
+
+/// Point-in-time view of a [`CircuitBreaker`] for health checks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct CircuitStatus {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// Time left until the next probe while open
+    pub retry_in: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// When the current probe was let through; a probe whose request was
+    /// dropped is replaced after another `open_for`
+    probe_started: Option<Instant>,
+}
+
+/// Stops sending requests to an API instance that keeps failing.
+///
+/// After `failure_threshold` consecutive connection errors, timeouts or `5xx` responses
+/// the circuit opens and requests fail with [`Error::CircuitOpen`] without
+/// reaching the server. Once `open_for` has passed a single probe request
+/// is let through: success closes the circuit, failure opens it again.
+/// Other requests that finish meanwhile do not change the state.
+/// Clones share their state.
+///
+/// # Examples
+///
+/// ```
+/// use osars::{CircuitBreaker, CircuitState, Client};
+/// use std::time::Duration;
+///
+/// let client = Client::new("https://api.thisishyum.ru/schedule_api/tyumen")
+///     .with_circuit_breaker(CircuitBreaker::new(5, Duration::from_secs(30)));
+///
+/// let breaker = client.circuit_breaker().unwrap();
+/// assert_eq!(breaker.state(), CircuitState::Closed);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    open_for: Duration,
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, open_for: Duration) -> Self {
+        Self {
+            failure_threshold: failure_threshold.max(1),
+            open_for,
+            inner: Arc::new(Mutex::new(Inner {
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_started: None,
+            })),
+        }
+    }
+
+    pub fn state(&self) -> CircuitState {
+        self.status().state
+    }
+
+    pub fn status(&self) -> CircuitStatus {
+        let inner = self.inner.lock().expect("circuit breaker lock poisoned");
+        self.status_at(&inner, Instant::now())
+    }
+
+    fn status_at(&self, inner: &Inner, now: Instant) -> CircuitStatus {
+        let (state, retry_in) = match inner.opened_at {
+            None => (CircuitState::Closed, None),
+            Some(opened_at) => {
+                let elapsed = now.duration_since(opened_at);
+                if elapsed < self.open_for {
+                    (CircuitState::Open, Some(self.open_for - elapsed))
+                } else {
+                    (CircuitState::HalfOpen, None)
+                }
+            }
+        };
+        CircuitStatus {
+            state,
+            consecutive_failures: inner.consecutive_failures,
+            retry_in,
+        }
+    }
+
+    /// Closes the circuit and forgets past failures
+    pub fn reset(&self) {
+        let mut inner = self.inner.lock().expect("circuit breaker lock poisoned");
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_started = None;
+    }
+
+    /// Fails with [`Error::CircuitOpen`] unless a request may be sent now
+    pub(crate) fn check(&self) -> Result<Permit> {
+        self.check_at(Instant::now())
+    }
+
+    fn check_at(&self, now: Instant) -> Result<Permit> {
+        let mut inner = self.inner.lock().expect("circuit breaker lock poisoned");
+        let status = self.status_at(&inner, now);
+        match status.state {
+            CircuitState::Closed => Ok(Permit { probe: None }),
+            CircuitState::HalfOpen
+                if inner
+                    .probe_started
+                    .is_none_or(|started| now.duration_since(started) >= self.open_for) =>
+            {
+                #[cfg(feature = "logging")]
+                debug!("Circuit half-open, sending probe request");
+                inner.probe_started = Some(now);
+                Ok(Permit { probe: Some(now) })
+            }
+            _ => Err(Error::CircuitOpen {
+                retry_in: status.retry_in.unwrap_or_default(),
+            }),
+        }
+    }
+
+    /// Records the outcome of a request let through by [`check`](Self::check)
+    pub(crate) fn record(&self, permit: Permit, success: bool) {
+        self.record_at(permit, success, Instant::now())
+    }
+
+    /// Returns a permit whose request failed without reaching the network
+    pub(crate) fn release(&self, permit: Permit) {
+        let mut inner = self.inner.lock().expect("circuit breaker lock poisoned");
+        if permit.probe.is_some() && inner.probe_started == permit.probe {
+            inner.probe_started = None;
+        }
+    }
+
+    fn record_at(&self, permit: Permit, success: bool, now: Instant) {
+        let mut inner = self.inner.lock().expect("circuit breaker lock poisoned");
+
+        match permit.probe {
+            // Only the current probe decides whether the circuit closes
+            Some(started) => {
+                if inner.probe_started != Some(started) {
+                    return;
+                }
+                inner.probe_started = None;
+                if success {
+                    inner.consecutive_failures = 0;
+                    inner.opened_at = None;
+                    return;
+                }
+                inner.consecutive_failures += 1;
+            }
+            // Requests sent before the circuit opened no longer count
+            None if inner.opened_at.is_some() => return,
+            None => {
+                if success {
+                    inner.consecutive_failures = 0;
+                    return;
+                }
+                inner.consecutive_failures += 1;
+                if inner.consecutive_failures < self.failure_threshold {
+                    return;
+                }
+            }
+        }
+
+        #[cfg(feature = "logging")]
+        warn!(
+            "Circuit opened after {} consecutive failures",
+            inner.consecutive_failures
+        );
+        inner.opened_at = Some(now);
+    }
+}
+
+/// Permission to send one request, returned by [`CircuitBreaker::check`].
+///
+/// Pass it back to [`CircuitBreaker::record`] with the request's outcome, or
+/// to [`CircuitBreaker::release`] if the outcome says nothing about the API.
+#[must_use]
+#[derive(Debug)]
+pub(crate) struct Permit {
+    /// Start of the probe if this request is one
+    probe: Option<Instant>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use mockito::Server;
+
+    #[test]
+    fn test_state_transitions() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(10));
+        let start = Instant::now();
+
+        let permit = breaker.check_at(start).unwrap();
+        breaker.record_at(permit, false, start);
+        let permit = breaker.check_at(start).unwrap();
+        breaker.record_at(permit, false, start);
+        assert!(matches!(
+            breaker.check_at(start + Duration::from_secs(4)),
+            Err(Error::CircuitOpen { retry_in }) if retry_in == Duration::from_secs(6)
+        ));
+
+        let later = start + Duration::from_secs(10);
+        let probe = breaker.check_at(later).unwrap();
+        assert!(breaker.check_at(later).is_err());
+        breaker.record_at(probe, false, later);
+        assert!(breaker.check_at(later + Duration::from_secs(1)).is_err());
+
+        let retry = later + Duration::from_secs(10);
+        let probe = breaker.check_at(retry).unwrap();
+        breaker.record_at(probe, true, retry);
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.status().consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_only_probe_decides_half_open_state() {
+        let breaker = CircuitBreaker::new(1, Duration::from_secs(10));
+        let start = Instant::now();
+
+        let slow = breaker.check_at(start).unwrap();
+        let failing = breaker.check_at(start).unwrap();
+        breaker.record_at(failing, false, start);
+
+        let later = start + Duration::from_secs(10);
+        let probe = breaker.check_at(later).unwrap();
+        // A request sent before the circuit opened finishes during the probe
+        breaker.record_at(slow, true, later);
+        assert!(breaker.check_at(later).is_err());
+
+        breaker.record_at(probe, false, later);
+        assert_eq!(
+            breaker
+                .status_at(&breaker.inner.lock().unwrap(), later)
+                .state,
+            CircuitState::Open
+        );
+
+        // A probe replaced after timing out no longer counts
+        let stale = breaker.check_at(later + Duration::from_secs(10)).unwrap();
+        let current = breaker.check_at(later + Duration::from_secs(20)).unwrap();
+        breaker.record_at(stale, true, later + Duration::from_secs(20));
+        assert!(breaker.inner.lock().unwrap().opened_at.is_some());
+        breaker.record_at(current, true, later + Duration::from_secs(20));
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_client_fails_fast_while_open() {
+        let mut server = Server::new_async().await;
+        let failing = server
+            .mock("GET", "/colleges")
+            .with_status(503)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url())
+            .with_circuit_breaker(CircuitBreaker::new(2, Duration::from_millis(50)));
+
+        for _ in 0..2 {
+            assert!(matches!(
+                client.colleges().send().await,
+                Err(Error::Api {
+                    status_code: 503,
+                    ..
+                })
+            ));
+        }
+        assert!(matches!(
+            client.clone().colleges().send().await,
+            Err(Error::CircuitOpen { .. })
+        ));
+        failing.assert_async().await;
+
+        failing.remove_async().await;
+        server
+            .mock("GET", "/colleges")
+            .with_body("[]")
+            .create_async()
+            .await;
+        tokio::time::sleep(Duration::from_millis(60)).await;
+
+        assert_eq!(
+            client.circuit_breaker().unwrap().state(),
+            CircuitState::HalfOpen
+        );
+        client.colleges().send().await.unwrap();
+        assert_eq!(
+            client.circuit_breaker().unwrap().state(),
+            CircuitState::Closed
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checks_after_rate_limit_pause() {
+        let mut server = Server::new_async().await;
+        let limited = server
+            .mock("GET", "/colleges")
+            .with_status(429)
+            .with_header("retry-after", "1")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url())
+            .with_circuit_breaker(CircuitBreaker::new(1, Duration::from_secs(30)));
+        assert!(client.colleges().send().await.is_err());
+        limited.assert_async().await;
+        limited.remove_async().await;
+        let skipped = server
+            .mock("GET", "/colleges")
+            .with_body("[]")
+            .expect(0)
+            .create_async()
+            .await;
+
+        let waiting = tokio::spawn({
+            let client = client.clone();
+            async move { client.colleges().send().await }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let breaker = client.circuit_breaker().unwrap();
+        let permit = breaker.check().unwrap();
+        breaker.record(permit, false);
+
+        assert!(matches!(
+            waiting.await.unwrap(),
+            Err(Error::CircuitOpen { .. })
+        ));
+        skipped.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_builder_errors_do_not_open() {
+        let client = Client::new("not a url")
+            .with_circuit_breaker(CircuitBreaker::new(1, Duration::from_secs(30)));
+
+        for _ in 0..2 {
+            assert!(matches!(
+                client.colleges().send().await,
+                Err(Error::Reqwest(e)) if e.is_builder()
+            ));
+        }
+        assert_eq!(
+            client
+                .circuit_breaker()
+                .unwrap()
+                .status()
+                .consecutive_failures,
+            0
+        );
+    }
+}
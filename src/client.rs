@@ -1,8 +1,10 @@
 use crate::Auth;
 use crate::api::{CampusQuery, CampusesQuery, CollegeQuery, CollegesQuery};
 use crate::auth::AuthenticatedClient;
+use crate::circuit::CircuitBreaker;
 use crate::error::Result;
-use crate::{GroupsQuery, ScheduleQuery, error::Error};
+use crate::rate_limit::{Budget, RateLimiter, retry_after};
+use crate::{GroupsQuery, ReminderEngine, ScheduleQuery, ScheduleWatcher, error::Error};
 /// A client for interacting with the educational schedule API.
 ///
 /// The `Client` provides methods to query colleges, campuses, groups, and schedules.
@@ -17,13 +19,15 @@ use crate::{GroupsQuery, ScheduleQuery, error::Error};
 ///     .with_college(1);
 /// ```
 #[cfg(feature = "logging")]
-use tracing::{debug, error};
+use tracing::{debug, error, warn};
 
 #[derive(Debug, Clone)]
 pub struct Client {
     pub(crate) base_url: String,
     pub http_client: reqwest::Client,
     pub(crate) default_college_id: Option<u32>,
+    pub(crate) rate_limiter: RateLimiter,
+    pub(crate) circuit_breaker: Option<CircuitBreaker>,
 }
 
 impl Client {
@@ -47,6 +51,8 @@ impl Client {
             base_url: base_url.to_string(),
             http_client: reqwest::Client::new(),
             default_college_id: None,
+            rate_limiter: RateLimiter::default(),
+            circuit_breaker: None,
         }
     }
 
@@ -75,6 +81,8 @@ impl Client {
             base_url: base_url.to_string(),
             http_client,
             default_college_id: None,
+            rate_limiter: RateLimiter::default(),
+            circuit_breaker: None,
         }
     }
 
@@ -96,6 +104,28 @@ impl Client {
         self
     }
 
+    /// Throttles requests with client-side token buckets.
+    ///
+    /// The limiter is shared by every clone of the client made afterwards,
+    /// see [`RateLimiter`].
+    pub fn with_rate_limiter(mut self, rate_limiter: RateLimiter) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Fails fast while the API keeps failing, see [`CircuitBreaker`].
+    ///
+    /// The breaker is shared by every clone of the client made afterwards.
+    pub fn with_circuit_breaker(mut self, circuit_breaker: CircuitBreaker) -> Self {
+        self.circuit_breaker = Some(circuit_breaker);
+        self
+    }
+
+    /// The circuit breaker, if any, e.g. to report its state in a health check
+    pub fn circuit_breaker(&self) -> Option<&CircuitBreaker> {
+        self.circuit_breaker.as_ref()
+    }
+
     /// Creates a query to list all colleges.
     ///
     /// # Examples
@@ -105,7 +135,7 @@ impl Client {
     /// let client = Client::new("https://api.example.com");
     /// let colleges_query = client.colleges();
     /// ```
-    pub fn colleges(&self) -> CollegesQuery {
+    pub fn colleges(&self) -> CollegesQuery<'_> {
         CollegesQuery::new(self)
     }
 
@@ -127,7 +157,7 @@ impl Client {
     ///     .with_college(1);
     /// let college_query = client.college().unwrap();
     /// ```
-    pub fn college(&self) -> Result<CollegeQuery> {
+    pub fn college(&self) -> Result<CollegeQuery<'_>> {
         let college_id = self.default_college_id.ok_or_else(|| {
             Error::Validation("No default college set. Use client.with_college() first".into())
         })?;
@@ -143,7 +173,7 @@ impl Client {
     /// # Errors
     ///
     /// Returns `Error::Validation` if no default college is set.
-    pub fn campuses(&self) -> Result<CampusesQuery> {
+    pub fn campuses(&self) -> Result<CampusesQuery<'_>> {
         let college_id = self
             .default_college_id
             .ok_or_else(|| Error::Validation("No default college set".into()))?;
@@ -163,7 +193,7 @@ impl Client {
     /// # Errors
     ///
     /// Returns `Error::Validation` if no default college is set.
-    pub fn campus(&self, campus_id: u32) -> Result<CampusQuery> {
+    pub fn campus(&self, campus_id: u32) -> Result<CampusQuery<'_>> {
         let _ = self.default_college_id.ok_or_else(|| {
             Error::Validation("No default college set. Use client.with_college() first".into())
         })?;
@@ -171,6 +201,13 @@ impl Client {
     }
 
     pub async fn get_json<T>(&self, path: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.get_json_with_auth(path, None).await
+    }
+
+    pub(crate) async fn get_json_with_auth<T>(&self, path: &str, auth: Option<&Auth>) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
@@ -178,12 +215,8 @@ impl Client {
         #[cfg(feature = "logging")]
         debug!("GET {}", url);
 
-        let response = self
-            .http_client
-            .get(&url)
-            .send()
-            .await
-            .map_err(crate::error::Error::Reqwest)?;
+        let request = self.http_client.get(&url);
+        let response = self.send(path, request, auth).await?;
 
         let status = response.status();
 
@@ -236,22 +269,44 @@ impl Client {
 
         let mut request = self.http_client.post(&url);
 
-        if let Some(auth) = auth {
-            request = auth.apply_to_request(request);
-        }
-
         if let Some(body) = body {
             request = request.json(body);
         }
 
-        let response = request.send().await.map_err(crate::error::Error::Reqwest)?;
+        let response = self.send(path, request, auth).await?;
+
+        self.handle_response(response).await
+    }
+
+    pub(crate) async fn patch_json<T, B>(
+        &self,
+        path: &str,
+        body: &B,
+        auth: Option<&Auth>,
+    ) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
+    {
+        let url = format!("{}{}", self.base_url, path);
+        #[cfg(feature = "logging")]
+        debug!("PATCH {}", url);
+
+        let request = self.http_client.patch(&url).json(body);
+        let response = self.send(path, request, auth).await?;
 
         self.handle_response(response).await
     }
 
-    pub(crate) async fn delete_json<T>(&self, path: &str, auth: Option<&Auth>) -> Result<T>
+    pub(crate) async fn delete_json<T, B>(
+        &self,
+        path: &str,
+        body: Option<&B>,
+        auth: Option<&Auth>,
+    ) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
+        B: serde::Serialize,
     {
         let url = format!("{}{}", self.base_url, path);
         #[cfg(feature = "logging")]
@@ -259,15 +314,83 @@ impl Client {
 
         let mut request = self.http_client.delete(&url);
 
-        if let Some(auth) = auth {
-            request = auth.apply_to_request(request);
+        if let Some(body) = body {
+            request = request.json(body);
         }
 
-        let response = request.send().await.map_err(crate::error::Error::Reqwest)?;
+        let response = self.send(path, request, auth).await?;
 
         self.handle_response(response).await
     }
 
+    /// Sends `request` with the token of `auth`.
+    ///
+    /// On `401 Unauthorized` the token provider is refreshed and the request
+    /// is retried once, if the provider can refresh and the body can be
+    /// replayed. Both attempts go through
+    /// the rate limiter and the circuit breaker.
+    async fn send(
+        &self,
+        path: &str,
+        request: reqwest::RequestBuilder,
+        auth: Option<&Auth>,
+    ) -> Result<reqwest::Response> {
+        let Some(auth) = auth.filter(|a| a.has_token()) else {
+            return self.send_once(path, request).await;
+        };
+
+        let retry = auth.can_refresh().then(|| request.try_clone()).flatten();
+        let response = self
+            .send_once(path, auth.authorize(request, false).await?)
+            .await?;
+
+        match retry {
+            Some(retry) if response.status() == reqwest::StatusCode::UNAUTHORIZED => {
+                #[cfg(feature = "logging")]
+                warn!("Token rejected with 401, refreshing and retrying");
+
+                self.send_once(path, auth.authorize(retry, true).await?)
+                    .await
+            }
+            _ => Ok(response),
+        }
+    }
+
+    async fn send_once(
+        &self,
+        path: &str,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response> {
+        let budget = Budget::for_path(path);
+        self.rate_limiter.acquire(budget).await;
+
+        // Checked after waiting so that a pause cannot outlive the breaker's decision
+        let permit = match &self.circuit_breaker {
+            Some(breaker) => Some(breaker.check()?),
+            None => None,
+        };
+        let result = request.send().await.map_err(crate::error::Error::Reqwest);
+
+        if let (Some(breaker), Some(permit)) = (&self.circuit_breaker, permit) {
+            match &result {
+                Ok(response) => breaker.record(permit, !response.status().is_server_error()),
+                Err(crate::error::Error::Reqwest(e)) if e.is_connect() || e.is_timeout() => {
+                    breaker.record(permit, false)
+                }
+                // e.g. a request that could not be built
+                Err(_) => breaker.release(permit),
+            }
+        }
+        let response = result?;
+
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+            && let Some(delay) = retry_after(response.headers())
+        {
+            self.rate_limiter.pause(budget, delay);
+        }
+        Ok(response)
+    }
+
     async fn handle_response<T>(&self, response: reqwest::Response) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
@@ -315,7 +438,7 @@ impl Client {
     /// # Arguments
     ///
     /// * `campus_id` - The ID of the campus
-    pub fn groups(&self, campus_id: u32) -> GroupsQuery {
+    pub fn groups(&self, campus_id: u32) -> GroupsQuery<'_> {
         GroupsQuery::new(self, campus_id)
     }
 
@@ -324,7 +447,7 @@ impl Client {
     /// # Arguments
     ///
     /// * `group_id` - The ID of the student group
-    pub fn schedule(&self, group_id: u32) -> ScheduleQuery {
+    pub fn schedule(&self, group_id: u32) -> ScheduleQuery<'_> {
         ScheduleQuery::new(self, group_id)
     }
 
@@ -333,7 +456,7 @@ impl Client {
     /// # Arguments
     ///
     /// * `group_id` - The ID of the student group
-    pub fn today(&self, group_id: u32) -> ScheduleQuery {
+    pub fn today(&self, group_id: u32) -> ScheduleQuery<'_> {
         self.schedule(group_id).today()
     }
 
@@ -342,9 +465,23 @@ impl Client {
     /// # Arguments
     ///
     /// * `group_id` - The ID of the student group
-    pub fn tomorrow(&self, group_id: u32) -> ScheduleQuery {
+    pub fn tomorrow(&self, group_id: u32) -> ScheduleQuery<'_> {
         self.schedule(group_id).tomorrow()
     }
+    /// Creates a watcher that polls group schedules and reports changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `group_ids` - IDs of the student groups to watch
+    pub fn watch(&self, group_ids: impl IntoIterator<Item = u32>) -> ScheduleWatcher {
+        ScheduleWatcher::new(self.clone(), group_ids)
+    }
+
+    /// Creates a reminder engine that fires ahead of upcoming lessons.
+    pub fn reminders(&self) -> ReminderEngine {
+        ReminderEngine::new(self.clone())
+    }
+
     /// Create an authenticated client for private endpoints
     pub fn authenticated(&self) -> AuthenticatedClient {
         AuthenticatedClient::new(self.clone())
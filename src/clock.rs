@@ -0,0 +1,126 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::Notify;
+
+/// Source of wall-clock time for long-running components.
+///
+/// Reminders and other timers go through this trait so that they can be
+/// driven by [`ManualClock`] in tests instead of real time.
+pub trait Clock: Clone + Send + Sync + 'static {
+    /// Returns the current instant
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Waits until the clock reaches `deadline`
+    fn sleep_until(&self, deadline: DateTime<Utc>) -> impl Future<Output = ()> + Send;
+}
+
+/// Clock backed by the system time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    async fn sleep_until(&self, deadline: DateTime<Utc>) {
+        if let Ok(duration) = (deadline - Utc::now()).to_std() {
+            tokio::time::sleep(duration).await;
+        }
+    }
+}
+
+/// Clock that only moves when told to.
+///
+/// Clones share the same time, so a test can keep one handle and advance it
+/// while the component under test sleeps on another.
+#[derive(Debug, Clone)]
+pub struct ManualClock {
+    now: Arc<Mutex<DateTime<Utc>>>,
+    notify: Arc<Notify>,
+}
+
+impl ManualClock {
+    pub fn new(now: DateTime<Utc>) -> Self {
+        Self {
+            now: Arc::new(Mutex::new(now)),
+            notify: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Moves the clock to `now` and wakes all sleepers
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock().unwrap() = now;
+        self.notify.notify_waiters();
+    }
+
+    /// Moves the clock forward by `delta` and wakes all sleepers
+    pub fn advance(&self, delta: TimeDelta) {
+        let now = self.now() + delta;
+        self.set(now);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+
+    async fn sleep_until(&self, deadline: DateTime<Utc>) {
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+
+            if self.now() >= deadline {
+                return;
+            }
+            notified.await;
+        }
+    }
+}
+
+/// Timezones with a DST switch for tests
+#[cfg(test)]
+pub(crate) mod test_tz {
+    use chrono::{FixedOffset, LocalResult, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+
+    /// UTC+2, switching to UTC+3 at 2024-03-31 01:00 UTC
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) struct Dst;
+
+    impl Dst {
+        const SWITCH: i64 = 1_711_846_800;
+
+        fn offset_at(timestamp: i64) -> FixedOffset {
+            let hours = if timestamp < Self::SWITCH { 2 } else { 3 };
+            FixedOffset::east_opt(hours * 3600).unwrap()
+        }
+    }
+
+    impl TimeZone for Dst {
+        type Offset = FixedOffset;
+
+        fn from_offset(_: &FixedOffset) -> Self {
+            Dst
+        }
+
+        fn offset_from_local_date(&self, local: &NaiveDate) -> LocalResult<FixedOffset> {
+            self.offset_from_local_datetime(&local.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_local_datetime(&self, local: &NaiveDateTime) -> LocalResult<FixedOffset> {
+            let standard = Self::offset_at(local.and_utc().timestamp() - 2 * 3600);
+            LocalResult::Single(standard)
+        }
+
+        fn offset_from_utc_date(&self, utc: &NaiveDate) -> FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_time(NaiveTime::MIN))
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &NaiveDateTime) -> FixedOffset {
+            Self::offset_at(utc.and_utc().timestamp())
+        }
+    }
+}
@@ -0,0 +1,423 @@
+use crate::{Call, Lesson, Schedule, Weekday};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// How serious a detected conflict is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Unusual but harmless, e.g. a lesson shifted against the bell schedule
+    Info,
+    /// Likely legitimate but worth checking, e.g. subgroups or a combined lecture
+    Warning,
+    /// Cannot happen in reality and points to bad data
+    Error,
+}
+
+/// A lesson together with the group it belongs to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Booking {
+    pub group_id: u32,
+    pub lesson: Lesson,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConflictKind {
+    /// One teacher has overlapping lessons in different groups
+    TeacherDoubleBooking {
+        teacher: String,
+        first: Booking,
+        second: Booking,
+    },
+    /// Different groups and teachers share a cabinet at the same time
+    CabinetDoubleBooking {
+        cabinet: String,
+        first: Booking,
+        second: Booking,
+    },
+    /// A lesson has no call for its order, or its times differ from the call
+    OutsideTimetable {
+        booking: Booking,
+        call: Option<Call>,
+    },
+    /// Lessons with different orders overlap within one group
+    OverlappingLessons {
+        group_id: u32,
+        first: Lesson,
+        second: Lesson,
+    },
+    /// A group has several lessons with the same order.
+    ///
+    /// A warning for subgroups, an error if two of the lessons are identical.
+    DuplicateOrder {
+        group_id: u32,
+        order: u32,
+        count: usize,
+    },
+}
+
+/// A problem found by [`detect_conflicts`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Conflict {
+    pub severity: Severity,
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub kind: ConflictKind,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} {}: ", self.severity, self.date)?;
+        match &self.kind {
+            ConflictKind::TeacherDoubleBooking {
+                teacher,
+                first,
+                second,
+            } => write!(
+                f,
+                "teacher '{}' is in groups {} and {} at once (orders {} and {})",
+                teacher, first.group_id, second.group_id, first.lesson.order, second.lesson.order
+            ),
+            ConflictKind::CabinetDoubleBooking {
+                cabinet,
+                first,
+                second,
+            } => write!(
+                f,
+                "cabinet '{}' is used by groups {} and {} at once (orders {} and {})",
+                cabinet, first.group_id, second.group_id, first.lesson.order, second.lesson.order
+            ),
+            ConflictKind::OutsideTimetable { booking, call } => match call {
+                Some(call) => write!(
+                    f,
+                    "group {} lesson {} runs {}–{} but the call is {}–{}",
+                    booking.group_id,
+                    booking.lesson.order,
+                    booking.lesson.start_time.format("%H:%M"),
+                    booking.lesson.end_time.format("%H:%M"),
+                    call.begins.format("%H:%M"),
+                    call.ends.format("%H:%M")
+                ),
+                None => write!(
+                    f,
+                    "group {} lesson {} has no call in the timetable",
+                    booking.group_id, booking.lesson.order
+                ),
+            },
+            ConflictKind::OverlappingLessons {
+                group_id,
+                first,
+                second,
+            } => write!(
+                f,
+                "group {} lessons {} and {} overlap",
+                group_id, first.order, second.order
+            ),
+            ConflictKind::DuplicateOrder {
+                group_id,
+                order,
+                count,
+            } => write!(
+                f,
+                "group {} has {} lessons with order {}",
+                group_id, count, order
+            ),
+        }
+    }
+}
+
+/// Checks schedules of several groups for double-bookings and inconsistencies.
+///
+/// `calls` is the college's bell schedule; pass an empty slice to skip
+/// timetable checks. Conflicts are sorted by date, most severe first.
+///
+/// # Examples
+///
+/// ```
+/// use osars::conflicts::detect_conflicts;
+///
+/// assert!(detect_conflicts(&[], &[]).is_empty());
+/// ```
+pub fn detect_conflicts(schedules: &[Schedule], calls: &[Call]) -> Vec<Conflict> {
+    let mut by_date: BTreeMap<NaiveDate, Vec<Booking>> = BTreeMap::new();
+    for schedule in schedules {
+        by_date
+            .entry(schedule.date)
+            .or_default()
+            .extend(schedule.lessons.iter().map(|lesson| Booking {
+                group_id: schedule.group_id,
+                lesson: lesson.clone(),
+            }));
+    }
+
+    let mut conflicts = Vec::new();
+    for (date, bookings) in by_date {
+        let mut push = |severity, kind| {
+            conflicts.push(Conflict {
+                severity,
+                date,
+                kind,
+            })
+        };
+
+        for (i, a) in bookings.iter().enumerate() {
+            for b in &bookings[i + 1..] {
+                if !overlaps(&a.lesson, &b.lesson) {
+                    continue;
+                }
+
+                if a.group_id == b.group_id {
+                    if a.lesson.order != b.lesson.order {
+                        push(
+                            Severity::Error,
+                            ConflictKind::OverlappingLessons {
+                                group_id: a.group_id,
+                                first: a.lesson.clone(),
+                                second: b.lesson.clone(),
+                            },
+                        );
+                    }
+                    continue;
+                }
+
+                let same_teacher =
+                    !a.lesson.teacher.is_empty() && a.lesson.teacher == b.lesson.teacher;
+                let same_cabinet =
+                    !a.lesson.cabinet.is_empty() && a.lesson.cabinet == b.lesson.cabinet;
+
+                if same_teacher {
+                    // The same room usually means a lecture shared by several groups
+                    let severity = if same_cabinet {
+                        Severity::Warning
+                    } else {
+                        Severity::Error
+                    };
+                    push(
+                        severity,
+                        ConflictKind::TeacherDoubleBooking {
+                            teacher: a.lesson.teacher.clone(),
+                            first: a.clone(),
+                            second: b.clone(),
+                        },
+                    );
+                } else if same_cabinet {
+                    push(
+                        Severity::Error,
+                        ConflictKind::CabinetDoubleBooking {
+                            cabinet: a.lesson.cabinet.clone(),
+                            first: a.clone(),
+                            second: b.clone(),
+                        },
+                    );
+                }
+            }
+        }
+
+        let mut orders: BTreeMap<(u32, u32), Vec<&Lesson>> = BTreeMap::new();
+        for booking in &bookings {
+            orders
+                .entry((booking.group_id, booking.lesson.order))
+                .or_default()
+                .push(&booking.lesson);
+        }
+        for ((group_id, order), lessons) in orders {
+            if lessons.len() > 1 {
+                // Subgroups legitimately share an order, identical lessons do not
+                let identical = lessons
+                    .iter()
+                    .enumerate()
+                    .any(|(i, a)| lessons[i + 1..].iter().any(|b| same_lesson(a, b)));
+                push(
+                    if identical {
+                        Severity::Error
+                    } else {
+                        Severity::Warning
+                    },
+                    ConflictKind::DuplicateOrder {
+                        group_id,
+                        order,
+                        count: lessons.len(),
+                    },
+                );
+            }
+        }
+
+        if !calls.is_empty() {
+            let weekday = Weekday::of(date);
+            for booking in &bookings {
+                let call = calls
+                    .iter()
+                    .find(|c| c.weekday == weekday && c.order == booking.lesson.order);
+                match call {
+                    None => push(
+                        Severity::Warning,
+                        ConflictKind::OutsideTimetable {
+                            booking: booking.clone(),
+                            call: None,
+                        },
+                    ),
+                    Some(call)
+                        if call.begins != booking.lesson.start_time
+                            || call.ends != booking.lesson.end_time =>
+                    {
+                        push(
+                            Severity::Info,
+                            ConflictKind::OutsideTimetable {
+                                booking: booking.clone(),
+                                call: Some(call.clone()),
+                            },
+                        )
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+    }
+
+    conflicts.sort_by(|a, b| a.date.cmp(&b.date).then(b.severity.cmp(&a.severity)));
+    conflicts
+}
+
+/// Same subject, teacher and cabinet; subgroups sharing an order differ in at least one
+fn same_lesson(a: &Lesson, b: &Lesson) -> bool {
+    a.title == b.title && a.teacher == b.teacher && a.cabinet == b.cabinet
+}
+
+fn overlaps(a: &Lesson, b: &Lesson) -> bool {
+    a.start_time < b.end_time && b.start_time < a.end_time
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn lesson(order: u32, teacher: &str, cabinet: &str, hour: u32) -> Lesson {
+        Lesson {
+            title: "Math".to_string(),
+            cabinet: cabinet.to_string(),
+            teacher: teacher.to_string(),
+            order,
+            start_time: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(hour + 1, 30, 0).unwrap(),
+        }
+    }
+
+    fn schedule(group_id: u32, lessons: Vec<Lesson>) -> Schedule {
+        Schedule {
+            group_id,
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            lessons,
+        }
+    }
+
+    fn kinds(conflicts: &[Conflict]) -> Vec<(Severity, &'static str)> {
+        conflicts
+            .iter()
+            .map(|c| {
+                let kind = match c.kind {
+                    ConflictKind::TeacherDoubleBooking { .. } => "teacher",
+                    ConflictKind::CabinetDoubleBooking { .. } => "cabinet",
+                    ConflictKind::OutsideTimetable { .. } => "timetable",
+                    ConflictKind::OverlappingLessons { .. } => "overlap",
+                    ConflictKind::DuplicateOrder { .. } => "duplicate",
+                };
+                (c.severity, kind)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_double_bookings() {
+        let schedules = [
+            schedule(
+                1,
+                vec![lesson(1, "Smith", "101", 8), lesson(2, "Curie", "202", 10)],
+            ),
+            schedule(
+                2,
+                vec![lesson(1, "Smith", "303", 8), lesson(2, "Bohr", "202", 10)],
+            ),
+            schedule(3, vec![lesson(1, "Smith", "101", 8)]),
+        ];
+        let conflicts = detect_conflicts(&schedules, &[]);
+
+        assert_eq!(
+            kinds(&conflicts),
+            vec![
+                (Severity::Error, "teacher"),
+                (Severity::Error, "cabinet"),
+                (Severity::Error, "teacher"),
+                (Severity::Warning, "teacher"),
+            ]
+        );
+        assert_eq!(
+            conflicts[1].to_string(),
+            "Error 2024-01-15: cabinet '202' is used by groups 1 and 2 at once (orders 2 and 2)"
+        );
+    }
+
+    #[test]
+    fn test_identical_lessons_in_one_order() {
+        let schedules = [schedule(
+            1,
+            vec![lesson(1, "Smith", "101", 8), lesson(1, "Smith", "101", 8)],
+        )];
+
+        assert_eq!(
+            kinds(&detect_conflicts(&schedules, &[])),
+            vec![(Severity::Error, "duplicate")]
+        );
+    }
+
+    #[test]
+    fn test_group_and_timetable_checks() {
+        let mut shifted = lesson(2, "Bohr", "102", 9);
+        shifted.start_time = NaiveTime::from_hms_opt(9, 15, 0).unwrap();
+        let schedules = [schedule(
+            1,
+            vec![
+                lesson(1, "Smith", "101", 8),
+                lesson(1, "Curie", "102", 8),
+                shifted,
+                lesson(5, "Smith", "101", 16),
+            ],
+        )];
+        let calls = [
+            Call {
+                call_id: 1,
+                weekday: Weekday::Monday,
+                begins: NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+                ends: NaiveTime::from_hms_opt(9, 30, 0).unwrap(),
+                order: 1,
+            },
+            Call {
+                call_id: 2,
+                weekday: Weekday::Monday,
+                begins: NaiveTime::from_hms_opt(9, 40, 0).unwrap(),
+                ends: NaiveTime::from_hms_opt(11, 10, 0).unwrap(),
+                order: 2,
+            },
+        ];
+        let conflicts = detect_conflicts(&schedules, &calls);
+
+        assert_eq!(
+            kinds(&conflicts),
+            vec![
+                (Severity::Error, "overlap"),
+                (Severity::Error, "overlap"),
+                (Severity::Warning, "duplicate"),
+                (Severity::Warning, "timetable"),
+                (Severity::Info, "timetable"),
+            ]
+        );
+
+        let json = serde_json::to_value(&conflicts[2]).unwrap();
+        assert_eq!(json["kind"], "duplicate_order");
+        assert_eq!(json["severity"], "warning");
+        assert_eq!(json["count"], 2);
+    }
+}
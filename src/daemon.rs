@@ -0,0 +1,728 @@
+use crate::clock::{Clock, SystemClock};
+use crate::error::{Error, Result};
+use crate::models::Weekday;
+use crate::source::{ParserRunner, RunReport, ScheduleSource};
+use chrono::{DateTime, Days, Local, NaiveDate, NaiveTime, TimeDelta, TimeZone, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+#[cfg(feature = "logging")]
+use tracing::{debug, warn};
+
+/// When a daemon job runs, similar to a cron entry.
+///
+/// Runs happen every `interval` starting at the window start (midnight by
+/// default) on the allowed weekdays, in the daemon's timezone.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::{NaiveTime, TimeDelta};
+/// use osars::daemon::Trigger;
+/// use osars::models::Weekday;
+///
+/// // Every 30 minutes from 08:00 to 18:00 on weekdays
+/// let trigger = Trigger::every(TimeDelta::minutes(30))
+///     .weekdays(&Weekday::ALL[..5])
+///     .between(
+///         NaiveTime::from_hms_opt(8, 0, 0).unwrap(),
+///         NaiveTime::from_hms_opt(18, 0, 0).unwrap(),
+///     );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trigger {
+    interval: TimeDelta,
+    weekdays: BTreeSet<Weekday>,
+    start: NaiveTime,
+    end: Option<NaiveTime>,
+}
+
+impl Trigger {
+    /// Runs every `interval`, clamped to between one minute and one day
+    pub fn every(interval: TimeDelta) -> Self {
+        Self {
+            interval: interval.clamp(TimeDelta::minutes(1), TimeDelta::days(1)),
+            weekdays: Weekday::ALL.into_iter().collect(),
+            start: NaiveTime::MIN,
+            end: None,
+        }
+    }
+
+    /// Runs once a day at `time`
+    pub fn daily_at(time: NaiveTime) -> Self {
+        Self::every(TimeDelta::days(1)).between(time, NaiveTime::MIN)
+    }
+
+    /// Restricts runs to the given weekdays
+    pub fn weekdays(mut self, weekdays: &[Weekday]) -> Self {
+        self.weekdays = weekdays.iter().copied().collect();
+        self
+    }
+
+    /// Restricts runs to `start..end`; an `end` of midnight means end of day
+    pub fn between(mut self, start: NaiveTime, end: NaiveTime) -> Self {
+        self.start = start;
+        self.end = (end != NaiveTime::MIN).then_some(end);
+        self
+    }
+
+    /// First run strictly after `after`, or `None` if no weekday is allowed.
+    ///
+    /// The UTC offset is looked up for each date, so runs keep their local
+    /// time across DST changes; slots skipped by a DST gap do not run.
+    pub fn next_after<Tz: TimeZone>(
+        &self,
+        after: DateTime<Utc>,
+        timezone: &Tz,
+    ) -> Option<DateTime<Utc>> {
+        let local = after.with_timezone(timezone).naive_local();
+        let interval = TimeDelta::seconds(self.interval.num_seconds());
+
+        for offset in 0..=7 {
+            let date = local.date() + Days::new(offset);
+            if !self.weekdays.contains(&Weekday::of(date)) {
+                continue;
+            }
+
+            let first = date.and_time(self.start);
+            let steps = if local < first {
+                0
+            } else {
+                (local - first).num_seconds() / interval.num_seconds() + 1
+            };
+            let mut candidate = first + interval * steps as i32;
+
+            while candidate.date() == date && self.end.is_none_or(|end| candidate.time() < end) {
+                let times = timezone.from_local_datetime(&candidate);
+                let next = [times.clone().earliest(), times.latest()]
+                    .into_iter()
+                    .flatten()
+                    .map(|t| t.with_timezone(&Utc))
+                    .find(|t| *t > after);
+                if next.is_some() {
+                    return next;
+                }
+                candidate += interval;
+            }
+        }
+
+        None
+    }
+}
+
+/// Something the daemon can run periodically.
+///
+/// Implemented for [`ParserRunner`]; `from` and `to` are today and the end
+/// of the daemon's horizon.
+pub trait Job: Send + Sync {
+    fn run(&self, from: NaiveDate, to: NaiveDate) -> BoxFuture<'_, Result<RunReport>>;
+}
+
+impl<S: ScheduleSource + 'static> Job for ParserRunner<S> {
+    fn run(&self, from: NaiveDate, to: NaiveDate) -> BoxFuture<'_, Result<RunReport>> {
+        Box::pin(self.run_for(from, to))
+    }
+}
+
+/// Persisted state of one job.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobStatus {
+    pub running: bool,
+    pub runs: u64,
+    pub failures: u64,
+    pub next_run: Option<DateTime<Utc>>,
+    pub last_started: Option<DateTime<Utc>>,
+    pub last_finished: Option<DateTime<Utc>>,
+    pub last_success: Option<DateTime<Utc>>,
+    /// Why the last run failed, `None` if it succeeded
+    pub last_error: Option<String>,
+}
+
+/// Status of all jobs, keyed by job name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaemonStatus {
+    pub jobs: BTreeMap<String, JobStatus>,
+}
+
+impl DaemonStatus {
+    /// Reads status from a JSON file, returning empty status if it doesn't exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(raw) => Ok(serde_json::from_str(&raw)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes status to a JSON file.
+    ///
+    /// The file is replaced atomically, so readers never see a partial write.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let raw = serde_json::to_string(self)?;
+        let mut temp = path.as_os_str().to_owned();
+        temp.push(".tmp");
+        std::fs::write(&temp, raw)?;
+        std::fs::rename(&temp, path)?;
+        Ok(())
+    }
+
+    /// Whether the last run of every job succeeded
+    pub fn is_healthy(&self) -> bool {
+        self.jobs.values().all(|j| j.last_error.is_none())
+    }
+}
+
+/// Shared view of a running daemon's status.
+#[derive(Debug, Clone, Default)]
+pub struct StatusHandle {
+    inner: Arc<Mutex<DaemonStatus>>,
+}
+
+impl StatusHandle {
+    /// Copy of the current status
+    pub fn snapshot(&self) -> DaemonStatus {
+        self.inner.lock().expect("status lock poisoned").clone()
+    }
+
+    fn update<R>(&self, f: impl FnOnce(&mut DaemonStatus) -> R) -> R {
+        f(&mut self.inner.lock().expect("status lock poisoned"))
+    }
+}
+
+struct StateFile {
+    path: PathBuf,
+    /// Serializes writes so that an older snapshot never replaces a newer one
+    writing: tokio::sync::Mutex<()>,
+}
+
+struct Entry {
+    name: String,
+    job: Arc<dyn Job>,
+    trigger: Trigger,
+}
+
+/// Runs registered jobs on their triggers until stopped.
+///
+/// Each job runs in its own loop and the next run is only scheduled after the
+/// previous one has finished, so runs of a job never overlap; slots missed
+/// during a long run are skipped.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(source: impl osars::source::ScheduleSource + 'static) -> osars::Result<()> {
+/// use chrono::TimeDelta;
+/// use osars::Client;
+/// use osars::daemon::{ParserDaemon, Trigger, serve_health};
+/// use osars::source::ParserRunner;
+///
+/// let client = Client::new("https://api.thisishyum.ru/schedule_api/tyumen").with_college(1);
+/// let runner = ParserRunner::new(client.authenticated().with_token("token").parser(), source);
+///
+/// let daemon = ParserDaemon::new()
+///     .register("college", runner, Trigger::every(TimeDelta::minutes(30)))
+///     .state_file("daemon.json");
+///
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:8080").await?;
+/// tokio::spawn(serve_health(listener, daemon.status()));
+/// daemon.run().await
+/// # }
+/// ```
+pub struct ParserDaemon<C: Clock = SystemClock, Tz: TimeZone = Local> {
+    jobs: Vec<Entry>,
+    clock: C,
+    timezone: Tz,
+    horizon: TimeDelta,
+    state_file: Option<StateFile>,
+    status: StatusHandle,
+}
+
+impl ParserDaemon {
+    pub fn new() -> Self {
+        Self {
+            jobs: Vec::new(),
+            clock: SystemClock,
+            timezone: Local,
+            horizon: TimeDelta::days(14),
+            state_file: None,
+            status: StatusHandle::default(),
+        }
+    }
+}
+
+impl Default for ParserDaemon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Clock, Tz: TimeZone> ParserDaemon<C, Tz> {
+    /// Adds a job under a unique name
+    pub fn register(mut self, name: &str, job: impl Job + 'static, trigger: Trigger) -> Self {
+        self.jobs.push(Entry {
+            name: name.to_string(),
+            job: Arc::new(job),
+            trigger,
+        });
+        self
+    }
+
+    /// Sets the timezone triggers are evaluated in (defaults to [`Local`])
+    pub fn timezone<T: TimeZone>(self, timezone: T) -> ParserDaemon<C, T> {
+        ParserDaemon {
+            jobs: self.jobs,
+            clock: self.clock,
+            timezone,
+            horizon: self.horizon,
+            state_file: self.state_file,
+            status: self.status,
+        }
+    }
+
+    /// Sets how far ahead of today jobs extract lessons (defaults to 14 days)
+    pub fn horizon(mut self, horizon: TimeDelta) -> Self {
+        self.horizon = horizon;
+        self
+    }
+
+    /// Persists job status to a JSON file, restoring it on start
+    pub fn state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_file = Some(StateFile {
+            path: path.into(),
+            writing: tokio::sync::Mutex::new(()),
+        });
+        self
+    }
+
+    /// Replaces the clock, e.g. with [`ManualClock`](crate::ManualClock) in tests
+    pub fn with_clock<T: Clock>(self, clock: T) -> ParserDaemon<T, Tz> {
+        ParserDaemon {
+            jobs: self.jobs,
+            clock,
+            timezone: self.timezone,
+            horizon: self.horizon,
+            state_file: self.state_file,
+            status: self.status,
+        }
+    }
+
+    /// Handle for inspecting status while the daemon runs
+    pub fn status(&self) -> StatusHandle {
+        self.status.clone()
+    }
+
+    /// Runs all jobs forever.
+    ///
+    /// Fails early if the state file cannot be read or two jobs share a name.
+    /// A state file that is not valid status JSON is ignored with a warning.
+    pub async fn run(self) -> Result<()> {
+        let names: BTreeSet<&str> = self.jobs.iter().map(|e| e.name.as_str()).collect();
+        if names.len() != self.jobs.len() {
+            return Err(Error::Validation("job names must be unique".to_string()));
+        }
+
+        let mut restored = match &self.state_file {
+            Some(state) => match DaemonStatus::load(&state.path) {
+                Err(Error::Serialization(_e)) => {
+                    #[cfg(feature = "logging")]
+                    warn!("Ignoring corrupt daemon state: {}", _e);
+                    DaemonStatus::default()
+                }
+                result => result?,
+            },
+            None => DaemonStatus::default(),
+        };
+        restored
+            .jobs
+            .retain(|name, _| names.contains(name.as_str()));
+        for entry in &self.jobs {
+            let job = restored.jobs.entry(entry.name.clone()).or_default();
+            job.running = false;
+        }
+        self.status.update(|status| *status = restored);
+
+        let daemon = &self;
+        futures::future::join_all(self.jobs.iter().map(|entry| daemon.job_loop(entry))).await;
+        Ok(())
+    }
+
+    async fn job_loop(&self, entry: &Entry) {
+        loop {
+            let Some(next) = entry.trigger.next_after(self.clock.now(), &self.timezone) else {
+                #[cfg(feature = "logging")]
+                warn!("Job '{}' has no runs scheduled", entry.name);
+                return;
+            };
+            self.update(&entry.name, |job| job.next_run = Some(next))
+                .await;
+            self.clock.sleep_until(next).await;
+
+            let started = self.clock.now();
+            self.update(&entry.name, |job| {
+                job.running = true;
+                job.last_started = Some(started);
+            })
+            .await;
+
+            let today = started.with_timezone(&self.timezone).date_naive();
+            let to = today + self.horizon;
+            #[cfg(feature = "logging")]
+            debug!("Running job '{}' for {}..={}", entry.name, today, to);
+
+            let error = match entry.job.run(today, to).await {
+                Ok(report) if report.is_success() => None,
+                Ok(report) => Some(format!(
+                    "{} problem(s), {} unknown campus(es), {} unknown group(s), {} failed chunk(s)",
+                    report.problems.len(),
+                    report.unknown_campuses.len(),
+                    report.unknown_groups.len(),
+                    report.upload.as_ref().map_or(0, |u| u.failed().count())
+                )),
+                Err(e) => Some(e.to_string()),
+            };
+
+            #[cfg(feature = "logging")]
+            if let Some(error) = &error {
+                warn!("Job '{}' failed: {}", entry.name, error);
+            }
+
+            let finished = self.clock.now();
+            self.update(&entry.name, |job| {
+                job.running = false;
+                job.runs += 1;
+                job.last_finished = Some(finished);
+                if error.is_some() {
+                    job.failures += 1;
+                } else {
+                    job.last_success = Some(finished);
+                }
+                job.last_error = error;
+            })
+            .await;
+        }
+    }
+
+    async fn update(&self, name: &str, f: impl FnOnce(&mut JobStatus)) {
+        self.status
+            .update(|status| f(status.jobs.entry(name.to_string()).or_default()));
+
+        let Some(state) = &self.state_file else {
+            return;
+        };
+        let _writing = state.writing.lock().await;
+        let snapshot = self.status.snapshot();
+        let path = state.path.clone();
+        let saved = tokio::task::spawn_blocking(move || snapshot.save(&path)).await;
+        if let Ok(Err(_e)) = saved {
+            #[cfg(feature = "logging")]
+            warn!("Failed to save daemon state: {}", _e);
+        }
+    }
+}
+
+/// Longest request head [`serve_health`] reads
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// How long [`serve_health`] waits for a complete request head
+const REQUEST_HEAD_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Serves `GET /health` with the daemon status as JSON.
+///
+/// Responds `200` when the last run of every job succeeded and `503`
+/// otherwise; other paths get `404`. Request heads longer than 8 KiB are
+/// answered with `431`; connections that send no complete head within 10
+/// seconds are closed.
+pub async fn serve_health(listener: TcpListener, status: StatusHandle) -> Result<()> {
+    loop {
+        let (mut stream, _) = listener.accept().await?;
+        let status = status.clone();
+
+        tokio::spawn(async move {
+            let mut head = Vec::new();
+            let mut buffer = [0u8; 1024];
+            let read_head = async {
+                while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_HEAD {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => return false,
+                        Ok(read) => head.extend_from_slice(&buffer[..read]),
+                    }
+                }
+                true
+            };
+            if !matches!(
+                tokio::time::timeout(REQUEST_HEAD_TIMEOUT, read_head).await,
+                Ok(true)
+            ) {
+                return;
+            }
+            let request = String::from_utf8_lossy(&head);
+            let path = request.split_whitespace().nth(1).unwrap_or("");
+
+            let (code, body) = if !head.windows(4).any(|w| w == b"\r\n\r\n") {
+                ("431 Request Header Fields Too Large", "{}".to_string())
+            } else if path == "/health" {
+                let snapshot = status.snapshot();
+                let code = if snapshot.is_healthy() {
+                    "200 OK"
+                } else {
+                    "503 Service Unavailable"
+                };
+                (code, serde_json::to_string(&snapshot).unwrap_or_default())
+            } else {
+                ("404 Not Found", "{}".to_string())
+            };
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                code,
+                body.len(),
+                body
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::test_tz::Dst;
+    use crate::{Campus, Client, ManualClock};
+    use chrono::FixedOffset;
+    use mockito::Server;
+    use std::time::Duration;
+
+    struct NoCampuses;
+
+    impl ScheduleSource for NoCampuses {
+        fn campuses(&self) -> BoxFuture<'_, Result<Vec<String>>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+
+        fn groups<'a>(&'a self, _campus: &'a Campus) -> BoxFuture<'a, Result<Vec<String>>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+
+        fn lessons<'a>(
+            &'a self,
+            _campus: &'a Campus,
+            _from: NaiveDate,
+            _to: NaiveDate,
+        ) -> BoxFuture<'a, Result<Vec<crate::source::SourceLesson>>> {
+            Box::pin(async { Ok(Vec::new()) })
+        }
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    async fn wait_for(mut condition: impl FnMut() -> bool) {
+        for _ in 0..500 {
+            if condition() {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(2)).await;
+        }
+        panic!("condition not reached");
+    }
+
+    #[test]
+    fn test_trigger_next_after() {
+        let tz = &FixedOffset::east_opt(5 * 3600).unwrap();
+        let trigger = Trigger::every(TimeDelta::minutes(30))
+            .weekdays(&Weekday::ALL[..5])
+            .between(time(8, 0), time(18, 0));
+
+        // Monday 07:10 local
+        assert_eq!(
+            trigger.next_after(utc("2024-01-15T02:10:00Z"), tz),
+            Some(utc("2024-01-15T03:00:00Z"))
+        );
+        // Monday 08:00 local exactly moves to 08:30
+        assert_eq!(
+            trigger.next_after(utc("2024-01-15T03:00:00Z"), tz),
+            Some(utc("2024-01-15T03:30:00Z"))
+        );
+        // Friday 17:45 local skips the weekend
+        assert_eq!(
+            trigger.next_after(utc("2024-01-19T12:45:00Z"), tz),
+            Some(utc("2024-01-22T03:00:00Z"))
+        );
+
+        let daily = Trigger::daily_at(time(6, 0));
+        assert_eq!(
+            daily.next_after(utc("2024-01-15T06:00:00Z"), &Utc),
+            Some(utc("2024-01-16T06:00:00Z"))
+        );
+        assert_eq!(
+            Trigger::every(TimeDelta::hours(1))
+                .weekdays(&[])
+                .next_after(Utc::now(), tz),
+            None
+        );
+    }
+
+    #[test]
+    fn test_trigger_keeps_local_time_across_dst() {
+        let daily = Trigger::daily_at(time(6, 0));
+
+        assert_eq!(
+            daily.next_after(utc("2024-03-29T05:00:00Z"), &Dst),
+            Some(utc("2024-03-30T04:00:00Z"))
+        );
+        assert_eq!(
+            daily.next_after(utc("2024-03-30T05:00:00Z"), &Dst),
+            Some(utc("2024-03-31T03:00:00Z"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_daemon_runs_on_schedule_and_persists_status() {
+        let mut server = Server::new_async().await;
+        let campuses = server
+            .mock("GET", "/colleges/1/campuses")
+            .with_body("[]")
+            .expect(2)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url()).with_college(1);
+        let runner = ParserRunner::new(
+            client.authenticated().with_token("token").parser(),
+            NoCampuses,
+        );
+        let clock = ManualClock::new(utc("2024-01-15T07:55:00Z"));
+        let state_file =
+            std::env::temp_dir().join(format!("osars-daemon-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&state_file);
+
+        let daemon = ParserDaemon::new()
+            .register("college", runner, Trigger::every(TimeDelta::minutes(30)))
+            .timezone(FixedOffset::east_opt(0).unwrap())
+            .state_file(&state_file)
+            .with_clock(clock.clone());
+        let status = daemon.status();
+        let task = tokio::spawn(daemon.run());
+
+        let job = |s: &StatusHandle| {
+            s.snapshot()
+                .jobs
+                .get("college")
+                .cloned()
+                .unwrap_or_default()
+        };
+        wait_for(|| job(&status).next_run == Some(utc("2024-01-15T08:00:00Z"))).await;
+        assert_eq!(job(&status).runs, 0);
+
+        clock.set(utc("2024-01-15T08:00:00Z"));
+        wait_for(|| job(&status).runs == 1).await;
+        wait_for(|| job(&status).next_run == Some(utc("2024-01-15T08:30:00Z"))).await;
+
+        // Several slots pass while waiting; only one run happens for them
+        clock.set(utc("2024-01-15T09:40:00Z"));
+        wait_for(|| job(&status).runs == 2).await;
+        wait_for(|| job(&status).next_run == Some(utc("2024-01-15T10:00:00Z"))).await;
+        task.abort();
+
+        campuses.assert_async().await;
+        let saved = DaemonStatus::load(&state_file).unwrap();
+        let _ = std::fs::remove_file(&state_file);
+        assert_eq!(saved.jobs["college"].runs, 2);
+        assert_eq!(
+            saved.jobs["college"].last_success,
+            Some(utc("2024-01-15T09:40:00Z"))
+        );
+        assert!(saved.is_healthy());
+    }
+
+    #[tokio::test]
+    async fn test_daemon_replaces_corrupt_state() {
+        let runner = ParserRunner::new(
+            Client::new("http://127.0.0.1:1")
+                .authenticated()
+                .with_token("token")
+                .parser(),
+            NoCampuses,
+        );
+        let state_file =
+            std::env::temp_dir().join(format!("osars-corrupt-{}.json", std::process::id()));
+        std::fs::write(&state_file, "{\"jobs\":").unwrap();
+
+        let daemon = ParserDaemon::new()
+            .register("college", runner, Trigger::every(TimeDelta::minutes(30)))
+            .state_file(&state_file)
+            .with_clock(ManualClock::new(utc("2024-01-15T07:55:00Z")));
+        let task = tokio::spawn(daemon.run());
+
+        wait_for(|| {
+            DaemonStatus::load(&state_file).is_ok_and(|s| s.jobs["college"].next_run.is_some())
+        })
+        .await;
+        assert!(!task.is_finished());
+        task.abort();
+        let _ = std::fs::remove_file(&state_file);
+    }
+
+    #[tokio::test]
+    async fn test_health_endpoint() {
+        let status = StatusHandle::default();
+        status.update(|s| {
+            s.jobs.insert(
+                "college".to_string(),
+                JobStatus {
+                    last_error: Some("API error 500: boom".to_string()),
+                    ..JobStatus::default()
+                },
+            )
+        });
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_health(listener, status.clone()));
+
+        let response = reqwest::get(format!("http://{}/health", address))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 503);
+        let body: DaemonStatus = response.json().await.unwrap();
+        assert_eq!(body, status.snapshot());
+
+        let missing = reqwest::get(format!("http://{}/other", address))
+            .await
+            .unwrap();
+        assert_eq!(missing.status(), 404);
+
+        let padded = reqwest::Client::new()
+            .get(format!("http://{}/health", address))
+            .header("x-padding", "a".repeat(4000))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(padded.status(), 503);
+        server.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_health_endpoint_closes_idle_connections() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = tokio::spawn(serve_health(listener, StatusHandle::default()));
+
+        let mut stream = tokio::net::TcpStream::connect(address).await.unwrap();
+        stream.write_all(b"GET /health HTTP/1.1\r\n").await.unwrap();
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response).await.unwrap();
+        assert!(response.is_empty());
+        server.abort();
+    }
+}
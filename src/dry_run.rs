@@ -0,0 +1,129 @@
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+/// A mutating request captured instead of being sent.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RecordedRequest {
+    /// `POST`, `PATCH` or `DELETE`
+    pub method: String,
+    /// Path relative to the client's base URL
+    pub path: String,
+    pub body: Option<serde_json::Value>,
+}
+
+/// Requests recorded by a dry-run [`AuthenticatedClient`](crate::AuthenticatedClient).
+///
+/// Clones share the same log, so APIs created from one client all write to it.
+#[derive(Debug, Clone, Default)]
+pub struct RequestLog {
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+}
+
+impl RequestLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn record(&self, request: RecordedRequest) {
+        #[cfg(feature = "logging")]
+        tracing::debug!("Dry run: {} {}", request.method, request.path);
+
+        self.requests
+            .lock()
+            .expect("request log lock poisoned")
+            .push(request);
+    }
+
+    /// Copies of all recorded requests in the order they were made
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests
+            .lock()
+            .expect("request log lock poisoned")
+            .clone()
+    }
+
+    pub fn len(&self) -> usize {
+        self.requests
+            .lock()
+            .expect("request log lock poisoned")
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Removes and returns all recorded requests
+    pub fn take(&self) -> Vec<RecordedRequest> {
+        std::mem::take(&mut *self.requests.lock().expect("request log lock poisoned"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Client, CreateParserRequest, Error, UpdateGroupsRequest};
+    use mockito::Server;
+
+    #[tokio::test]
+    async fn test_dry_run_records_mutations_and_allows_reads() {
+        let mut server = Server::new_async().await;
+        let read = server
+            .mock("GET", "/colleges")
+            .with_body("[]")
+            .expect(1)
+            .create_async()
+            .await;
+        let writes = server
+            .mock("POST", mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+        let deletes = server
+            .mock("DELETE", mockito::Matcher::Any)
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url())
+            .authenticated()
+            .with_token("token")
+            .dry_run();
+        let log = client.request_log.clone().unwrap();
+
+        client
+            .parser()
+            .update_groups(UpdateGroupsRequest {
+                campus_id: 3,
+                student_group_names: vec!["A".to_string()],
+            })
+            .await
+            .unwrap();
+        let created = client
+            .admin()
+            .create_parser(CreateParserRequest {
+                college_name: "College".to_string(),
+                campus_names: vec![],
+            })
+            .await;
+        client.admin().delete_parser(7).await.unwrap();
+        client.client.colleges().send().await.unwrap();
+
+        read.assert_async().await;
+        writes.assert_async().await;
+        deletes.assert_async().await;
+
+        assert!(matches!(
+            created,
+            Err(Error::DryRun { path, .. }) if path == "/admin/parser"
+        ));
+        let requests = log.take();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/parser/groups");
+        assert_eq!(requests[0].body.as_ref().unwrap()["campusId"], 3);
+        assert_eq!(requests[2].method, "DELETE");
+        assert_eq!(requests[2].path, "/admin/parser/7");
+        assert!(requests[2].body.is_none());
+        assert!(log.is_empty());
+    }
+}
@@ -16,19 +16,44 @@ pub enum Error {
 
     #[error("Validation error: {0}")]
     Validation(String),
+
+    #[error(
+        "Invalid request: {}",
+        .0.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    )]
+    InvalidRequest(Vec<crate::models::Problem>),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Circuit breaker open, retry in {retry_in:?}")]
+    CircuitOpen { retry_in: std::time::Duration },
+
+    #[error("Dry run: {method} {path} was recorded but not sent, so it has no response")]
+    DryRun { method: String, path: String },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 impl Error {
+    /// Whether the request may succeed if sent again: network errors,
+    /// `429 Too Many Requests` and server errors.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Error::Reqwest(_) => true,
+            Error::Api { status_code, .. } => *status_code == 429 || *status_code >= 500,
+            _ => false,
+        }
+    }
+
     pub fn from_response(status: u16, body: String) -> Self {
-        if let Ok(error_response) = serde_json::from_str::<serde_json::Value>(&body) {
-            if let Some(message) = error_response.get("error").and_then(|v| v.as_str()) {
-                return Error::Api {
-                    status_code: status,
-                    message: message.to_string(),
-                };
-            }
+        if let Ok(error_response) = serde_json::from_str::<serde_json::Value>(&body)
+            && let Some(message) = error_response.get("error").and_then(|v| v.as_str())
+        {
+            return Error::Api {
+                status_code: status,
+                message: message.to_string(),
+            };
         }
 
         Error::Api {
@@ -1,13 +1,41 @@
+pub mod analytics;
 pub mod api;
 pub mod auth;
+pub mod circuit;
 pub mod client;
+pub mod clock;
+pub mod conflicts;
+pub mod daemon;
+pub mod dry_run;
 pub mod error;
+pub mod locale;
 pub mod logging;
 pub mod models;
+pub mod notify;
+pub mod plan;
+pub mod rate_limit;
+pub mod reminder;
+pub mod render;
+pub mod secret;
+pub mod source;
+pub mod token;
 pub mod utils;
+pub mod watch;
 
+pub use analytics::ScheduleReport;
 pub use api::*;
 pub use auth::*;
+pub use circuit::{CircuitBreaker, CircuitState, CircuitStatus};
 pub use client::*;
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use dry_run::{RecordedRequest, RequestLog};
 pub use error::{Error, Result};
 pub use models::*;
+pub use notify::{Notification, NotificationSink};
+pub use plan::DayPlan;
+pub use rate_limit::{Budget, RateLimit, RateLimiter};
+pub use reminder::{Reminder, ReminderEngine, Subscription};
+pub use secret::{CredentialProvider, EnvCredentials, FileCredentials, SecretToken};
+pub use source::{ParserRunner, ScheduleSource};
+pub use token::{TokenCallback, TokenProvider, WatchedTokenFile};
+pub use watch::{ScheduleChange, ScheduleWatcher, WatchState};
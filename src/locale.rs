@@ -0,0 +1,249 @@
+use crate::models::{Day, Week, Weekday};
+use std::sync::Arc;
+
+/// Length of a localized name.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameStyle {
+    /// e.g. "Monday" / "Понедельник"
+    #[default]
+    Full,
+    /// e.g. "Mon" / "Пн"
+    Short,
+}
+
+/// Fixed labels used by text renderers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Label {
+    Order,
+    Time,
+    Title,
+    Teacher,
+    Cabinet,
+    NoLessons,
+}
+
+/// Human-readable names for schedule enums and renderer labels.
+///
+/// This is separate from the `Display` impls of [`Weekday`], [`Day`] and
+/// [`Week`], which produce the lowercase tokens expected by the API.
+/// Implement it to add a language; [`English`] and [`Russian`] are built in.
+///
+/// # Examples
+///
+/// ```
+/// use osars::locale::{Locale, NameStyle, Russian};
+/// use osars::models::{Day, Weekday};
+///
+/// assert_eq!(Russian.weekday(&Weekday::Monday, NameStyle::Short), "Пн");
+/// assert_eq!(Russian.day(&Day::Tomorrow), "Завтра");
+/// ```
+pub trait Locale: Send + Sync {
+    fn weekday(&self, weekday: &Weekday, style: NameStyle) -> &str;
+    fn day(&self, day: &Day) -> &str;
+    fn week(&self, week: &Week) -> &str;
+    fn label(&self, label: Label) -> &str;
+}
+
+impl<L: Locale + ?Sized> Locale for Arc<L> {
+    fn weekday(&self, weekday: &Weekday, style: NameStyle) -> &str {
+        (**self).weekday(weekday, style)
+    }
+
+    fn day(&self, day: &Day) -> &str {
+        (**self).day(day)
+    }
+
+    fn week(&self, week: &Week) -> &str {
+        (**self).week(week)
+    }
+
+    fn label(&self, label: Label) -> &str {
+        (**self).label(label)
+    }
+}
+
+/// English names.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+impl Locale for English {
+    fn weekday(&self, weekday: &Weekday, style: NameStyle) -> &str {
+        let (full, short) = match weekday {
+            Weekday::Monday => ("Monday", "Mon"),
+            Weekday::Tuesday => ("Tuesday", "Tue"),
+            Weekday::Wednesday => ("Wednesday", "Wed"),
+            Weekday::Thursday => ("Thursday", "Thu"),
+            Weekday::Friday => ("Friday", "Fri"),
+            Weekday::Saturday => ("Saturday", "Sat"),
+            Weekday::Sunday => ("Sunday", "Sun"),
+        };
+        match style {
+            NameStyle::Full => full,
+            NameStyle::Short => short,
+        }
+    }
+
+    fn day(&self, day: &Day) -> &str {
+        match day {
+            Day::Today => "Today",
+            Day::Tomorrow => "Tomorrow",
+        }
+    }
+
+    fn week(&self, week: &Week) -> &str {
+        match week {
+            Week::Previous => "Previous week",
+            Week::Current => "Current week",
+            Week::Next => "Next week",
+        }
+    }
+
+    fn label(&self, label: Label) -> &str {
+        match label {
+            Label::Order => "#",
+            Label::Time => "Time",
+            Label::Title => "Title",
+            Label::Teacher => "Teacher",
+            Label::Cabinet => "Cabinet",
+            Label::NoLessons => "No lessons",
+        }
+    }
+}
+
+/// Russian names.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Russian;
+
+impl Locale for Russian {
+    fn weekday(&self, weekday: &Weekday, style: NameStyle) -> &str {
+        let (full, short) = match weekday {
+            Weekday::Monday => ("Понедельник", "Пн"),
+            Weekday::Tuesday => ("Вторник", "Вт"),
+            Weekday::Wednesday => ("Среда", "Ср"),
+            Weekday::Thursday => ("Четверг", "Чт"),
+            Weekday::Friday => ("Пятница", "Пт"),
+            Weekday::Saturday => ("Суббота", "Сб"),
+            Weekday::Sunday => ("Воскресенье", "Вс"),
+        };
+        match style {
+            NameStyle::Full => full,
+            NameStyle::Short => short,
+        }
+    }
+
+    fn day(&self, day: &Day) -> &str {
+        match day {
+            Day::Today => "Сегодня",
+            Day::Tomorrow => "Завтра",
+        }
+    }
+
+    fn week(&self, week: &Week) -> &str {
+        match week {
+            Week::Previous => "Прошлая неделя",
+            Week::Current => "Текущая неделя",
+            Week::Next => "Следующая неделя",
+        }
+    }
+
+    fn label(&self, label: Label) -> &str {
+        match label {
+            Label::Order => "№",
+            Label::Time => "Время",
+            Label::Title => "Предмет",
+            Label::Teacher => "Преподаватель",
+            Label::Cabinet => "Кабинет",
+            Label::NoLessons => "Нет занятий",
+        }
+    }
+}
+
+/// Built-in locales, for selecting a language by value.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    En,
+    Ru,
+}
+
+impl Language {
+    fn locale(&self) -> &'static dyn Locale {
+        match self {
+            Language::En => &English,
+            Language::Ru => &Russian,
+        }
+    }
+}
+
+impl Locale for Language {
+    fn weekday(&self, weekday: &Weekday, style: NameStyle) -> &str {
+        self.locale().weekday(weekday, style)
+    }
+
+    fn day(&self, day: &Day) -> &str {
+        self.locale().day(day)
+    }
+
+    fn week(&self, week: &Week) -> &str {
+        self.locale().week(week)
+    }
+
+    fn label(&self, label: Label) -> &str {
+        self.locale().label(label)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Shouting;
+
+    impl Locale for Shouting {
+        fn weekday(&self, _weekday: &Weekday, _style: NameStyle) -> &str {
+            "DAY"
+        }
+
+        fn day(&self, _day: &Day) -> &str {
+            "NOW"
+        }
+
+        fn week(&self, _week: &Week) -> &str {
+            "WEEK"
+        }
+
+        fn label(&self, _label: Label) -> &str {
+            "LABEL"
+        }
+    }
+
+    #[test]
+    fn test_builtin_names() {
+        assert_eq!(
+            English.weekday(&Weekday::Wednesday, NameStyle::Full),
+            "Wednesday"
+        );
+        assert_eq!(
+            English.weekday(&Weekday::Wednesday, NameStyle::Short),
+            "Wed"
+        );
+        assert_eq!(Russian.week(&Week::Next), "Следующая неделя");
+        assert_eq!(Language::Ru.day(&Day::Today), "Сегодня");
+    }
+
+    #[test]
+    fn test_wire_format_is_unchanged() {
+        assert_eq!(Weekday::Monday.to_string(), "monday");
+        assert_eq!(
+            Language::Ru.weekday(&Weekday::Monday, NameStyle::Full),
+            "Понедельник"
+        );
+    }
+
+    #[test]
+    fn test_custom_locale_as_trait_object() {
+        let locale: Arc<dyn Locale> = Arc::new(Shouting);
+        assert_eq!(locale.weekday(&Weekday::Friday, NameStyle::Full), "DAY");
+        assert_eq!(locale.label(Label::NoLessons), "LABEL");
+    }
+}
@@ -1,16 +1,19 @@
+use super::Weekday;
 use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Represents a call (lesson period) in the college schedule.
 ///
 /// Defines the time periods for lessons throughout the day.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Call {
     /// Unique identifier for the call
     #[serde(rename = "callId")]
     pub call_id: u32,
-    /// Day of the week (1-7, where 1 is Monday)
-    pub weekday: u8,
+    /// Day of the week, sent as 1-7 where 1 is Monday
+    #[serde(with = "crate::utils::weekday_serde")]
+    pub weekday: Weekday,
     /// Time when the lesson period begins
     #[serde(with = "crate::utils::time_serde")]
     pub begins: NaiveTime,
@@ -20,3 +23,15 @@ pub struct Call {
     /// Order of the call in the daily schedule
     pub order: u32,
 }
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}. {}–{}",
+            self.order,
+            self.begins.format("%H:%M"),
+            self.ends.format("%H:%M")
+        )
+    }
+}
@@ -1,10 +1,11 @@
 use chrono::NaiveTime;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Represents a single lesson in a schedule.
 ///
 /// Contains details about a specific class session.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Lesson {
     /// Title or name of the lesson
     pub title: String,
@@ -21,3 +22,9 @@ pub struct Lesson {
     #[serde(rename = "endTime", with = "crate::utils::time_serde")]
     pub end_time: NaiveTime,
 }
+
+impl fmt::Display for Lesson {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&crate::render::Renderer::default().lesson(self))
+    }
+}
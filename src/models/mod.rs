@@ -5,24 +5,32 @@ pub mod group;
 pub mod lesson;
 pub mod requests;
 pub mod schedule;
+pub mod validation;
 
+use crate::error::Error;
 pub use call::Call;
 pub use campus::Campus;
+use chrono::{Datelike, Days, NaiveDate};
 pub use college::College;
 pub use group::Group;
 pub use lesson::Lesson;
 pub use requests::*;
 pub use schedule::Schedule;
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
+pub use validation::{Problem, Validate};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Week {
     Previous,
     Current,
     Next,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Weekday {
     Monday,
     Tuesday,
@@ -33,7 +41,8 @@ pub enum Weekday {
     Sunday,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Day {
     Today,
     Tomorrow,
@@ -72,6 +81,156 @@ impl fmt::Display for Day {
     }
 }
 
+impl Week {
+    /// Monday and Sunday of this week relative to `today`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use osars::models::Week;
+    ///
+    /// let today = NaiveDate::from_ymd_opt(2024, 1, 17).unwrap();
+    /// let (monday, sunday) = Week::Next.range(today);
+    /// assert_eq!(monday, NaiveDate::from_ymd_opt(2024, 1, 22).unwrap());
+    /// assert_eq!(sunday, NaiveDate::from_ymd_opt(2024, 1, 28).unwrap());
+    /// ```
+    pub fn range(&self, today: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let monday = today.week(chrono::Weekday::Mon).first_day();
+        let monday = match self {
+            Week::Previous => monday - Days::new(7),
+            Week::Current => monday,
+            Week::Next => monday + Days::new(7),
+        };
+        (monday, monday + Days::new(6))
+    }
+}
+
+impl Weekday {
+    /// All weekdays starting from Monday
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    /// Weekday of a date
+    pub fn of(date: NaiveDate) -> Self {
+        date.weekday().into()
+    }
+
+    /// Day number as used by the API (1 is Monday, 7 is Sunday)
+    pub fn number_from_monday(&self) -> u8 {
+        chrono::Weekday::from(*self).number_from_monday() as u8
+    }
+}
+
+impl Day {
+    /// Calendar date this day refers to relative to `today`
+    pub fn date(&self, today: NaiveDate) -> NaiveDate {
+        match self {
+            Day::Today => today,
+            Day::Tomorrow => today + Days::new(1),
+        }
+    }
+}
+
+impl From<chrono::Weekday> for Weekday {
+    fn from(weekday: chrono::Weekday) -> Self {
+        match weekday {
+            chrono::Weekday::Mon => Weekday::Monday,
+            chrono::Weekday::Tue => Weekday::Tuesday,
+            chrono::Weekday::Wed => Weekday::Wednesday,
+            chrono::Weekday::Thu => Weekday::Thursday,
+            chrono::Weekday::Fri => Weekday::Friday,
+            chrono::Weekday::Sat => Weekday::Saturday,
+            chrono::Weekday::Sun => Weekday::Sunday,
+        }
+    }
+}
+
+impl From<Weekday> for chrono::Weekday {
+    fn from(weekday: Weekday) -> Self {
+        match weekday {
+            Weekday::Monday => chrono::Weekday::Mon,
+            Weekday::Tuesday => chrono::Weekday::Tue,
+            Weekday::Wednesday => chrono::Weekday::Wed,
+            Weekday::Thursday => chrono::Weekday::Thu,
+            Weekday::Friday => chrono::Weekday::Fri,
+            Weekday::Saturday => chrono::Weekday::Sat,
+            Weekday::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+impl TryFrom<u8> for Weekday {
+    type Error = Error;
+
+    /// Converts an API day number (1 is Monday, 7 is Sunday)
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1..=7 => Ok(Weekday::ALL[value as usize - 1]),
+            _ => Err(Error::Validation(format!(
+                "weekday must be between 1 and 7, got {}",
+                value
+            ))),
+        }
+    }
+}
+
+impl From<Weekday> for u8 {
+    fn from(weekday: Weekday) -> Self {
+        weekday.number_from_monday()
+    }
+}
+
+impl FromStr for Week {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "previous" => Ok(Week::Previous),
+            "current" => Ok(Week::Current),
+            "next" => Ok(Week::Next),
+            _ => Err(Error::Validation(format!("unknown week '{}'", s))),
+        }
+    }
+}
+
+impl FromStr for Weekday {
+    type Err = Error;
+
+    /// Parses full or three-letter English names, case-insensitively
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "monday" | "mon" => Ok(Weekday::Monday),
+            "tuesday" | "tue" => Ok(Weekday::Tuesday),
+            "wednesday" | "wed" => Ok(Weekday::Wednesday),
+            "thursday" | "thu" => Ok(Weekday::Thursday),
+            "friday" | "fri" => Ok(Weekday::Friday),
+            "saturday" | "sat" => Ok(Weekday::Saturday),
+            "sunday" | "sun" => Ok(Weekday::Sunday),
+            _ => Err(Error::Validation(format!("unknown weekday '{}'", s))),
+        }
+    }
+}
+
+impl FromStr for Day {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "today" => Ok(Day::Today),
+            "tomorrow" => Ok(Day::Tomorrow),
+            _ => Err(Error::Validation(format!("unknown day '{}'", s))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,6 +260,74 @@ mod tests {
         assert_eq!(Day::Tomorrow.to_string(), "tomorrow");
     }
 
+    #[test]
+    fn test_enum_serde_matches_display() {
+        for weekday in Weekday::ALL {
+            let json = serde_json::to_string(&weekday).unwrap();
+            assert_eq!(json, format!("\"{}\"", weekday));
+            assert_eq!(serde_json::from_str::<Weekday>(&json).unwrap(), weekday);
+            assert_eq!(weekday.to_string().parse::<Weekday>().unwrap(), weekday);
+        }
+        assert_eq!(serde_json::to_string(&Week::Next).unwrap(), "\"next\"");
+        assert_eq!(
+            serde_json::from_str::<Day>("\"today\"").unwrap(),
+            Day::Today
+        );
+        assert_eq!("Tomorrow".parse::<Day>().unwrap(), Day::Tomorrow);
+        assert_eq!("PREVIOUS".parse::<Week>().unwrap(), Week::Previous);
+        assert!("someday".parse::<Day>().is_err());
+    }
+
+    #[test]
+    fn test_weekday_conversions() {
+        assert_eq!(Weekday::try_from(1).unwrap(), Weekday::Monday);
+        assert_eq!(Weekday::try_from(7).unwrap(), Weekday::Sunday);
+        assert!(Weekday::try_from(0).is_err());
+        assert!(Weekday::try_from(8).is_err());
+        assert_eq!(u8::from(Weekday::Wednesday), 3);
+        assert_eq!(chrono::Weekday::from(Weekday::Friday), chrono::Weekday::Fri);
+        assert_eq!(Weekday::from(chrono::Weekday::Sat), Weekday::Saturday);
+        assert_eq!("fri".parse::<Weekday>().unwrap(), Weekday::Friday);
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        assert_eq!(Weekday::of(date), Weekday::Monday);
+    }
+
+    #[test]
+    fn test_week_range() {
+        let sunday = NaiveDate::from_ymd_opt(2024, 1, 21).unwrap();
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        assert_eq!(Week::Current.range(sunday), (monday, sunday));
+        assert_eq!(Week::Current.range(monday), (monday, sunday));
+        assert_eq!(
+            Week::Previous.range(monday),
+            (
+                NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 1, 14).unwrap()
+            )
+        );
+        assert_eq!(
+            Day::Tomorrow.date(sunday),
+            NaiveDate::from_ymd_opt(2024, 1, 22).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_call_weekday_is_numeric() {
+        let json = r#"{"callId":1,"weekday":3,"begins":"09:00:00","ends":"10:30:00","order":1}"#;
+        let call: Call = serde_json::from_str(json).unwrap();
+        assert_eq!(call.weekday, Weekday::Wednesday);
+        assert!(
+            serde_json::to_string(&call)
+                .unwrap()
+                .contains("\"weekday\":3")
+        );
+
+        let invalid = json.replace("\"weekday\":3", "\"weekday\":9");
+        assert!(serde_json::from_str::<Call>(&invalid).is_err());
+    }
+
     #[test]
     fn test_college_serialization() {
         let college = College {
@@ -1,3 +1,7 @@
+use super::validation::{DATE_FORMAT, TIME_FORMAT};
+use super::{Call, Campus, Lesson, Schedule, Weekday};
+use crate::error::{Error, Result};
+use chrono::{NaiveDate, NaiveTime};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,7 +25,8 @@ pub struct UpdateCallsRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallRequest {
-    pub weekday: u8,
+    #[serde(with = "crate::utils::weekday_serde")]
+    pub weekday: Weekday,
     pub begins: String,
     pub ends: String,
     pub order: u32,
@@ -43,7 +48,330 @@ pub struct LessonRequest {
     pub date: String,
 }
 
+impl CallRequest {
+    pub fn new(weekday: Weekday, begins: NaiveTime, ends: NaiveTime, order: u32) -> Self {
+        Self {
+            weekday,
+            begins: begins.format(TIME_FORMAT).to_string(),
+            ends: ends.format(TIME_FORMAT).to_string(),
+            order,
+        }
+    }
+}
+
+impl From<&Call> for CallRequest {
+    fn from(call: &Call) -> Self {
+        Self::new(call.weekday, call.begins, call.ends, call.order)
+    }
+}
+
+impl From<&[Call]> for UpdateCallsRequest {
+    fn from(calls: &[Call]) -> Self {
+        Self {
+            calls: calls.iter().map(CallRequest::from).collect(),
+        }
+    }
+}
+
+impl LessonRequest {
+    /// Starts a typed builder for a lesson.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use chrono::NaiveDate;
+    /// use osars::LessonRequest;
+    ///
+    /// let lesson = LessonRequest::builder()
+    ///     .group_id(1)
+    ///     .order(2)
+    ///     .title("Math")
+    ///     .date(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap())
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(lesson.date, "2024-01-15");
+    /// ```
+    pub fn builder() -> LessonRequestBuilder {
+        LessonRequestBuilder::default()
+    }
+
+    /// Converts a lesson read from a schedule back into a request
+    pub fn from_lesson(schedule: &Schedule, lesson: &Lesson) -> Self {
+        Self {
+            group_id: schedule.group_id,
+            order: lesson.order,
+            title: lesson.title.clone(),
+            teacher: lesson.teacher.clone(),
+            cabinet: lesson.cabinet.clone(),
+            date: schedule.date.format(DATE_FORMAT).to_string(),
+        }
+    }
+}
+
+impl From<&Schedule> for UpdateLessonsRequest {
+    fn from(schedule: &Schedule) -> Self {
+        Self::from(std::slice::from_ref(schedule))
+    }
+}
+
+impl From<&[Schedule]> for UpdateLessonsRequest {
+    fn from(schedules: &[Schedule]) -> Self {
+        Self {
+            lessons: schedules
+                .iter()
+                .flat_map(|s| s.lessons.iter().map(|l| LessonRequest::from_lesson(s, l)))
+                .collect(),
+        }
+    }
+}
+
+/// Builder for [`LessonRequest`]; teacher and cabinet default to empty.
+#[derive(Debug, Clone, Default)]
+pub struct LessonRequestBuilder {
+    group_id: Option<u32>,
+    order: Option<u32>,
+    title: Option<String>,
+    teacher: String,
+    cabinet: String,
+    date: Option<NaiveDate>,
+}
+
+impl LessonRequestBuilder {
+    pub fn group_id(mut self, group_id: u32) -> Self {
+        self.group_id = Some(group_id);
+        self
+    }
+
+    pub fn order(mut self, order: u32) -> Self {
+        self.order = Some(order);
+        self
+    }
+
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    pub fn teacher(mut self, teacher: &str) -> Self {
+        self.teacher = teacher.to_string();
+        self
+    }
+
+    pub fn cabinet(mut self, cabinet: &str) -> Self {
+        self.cabinet = cabinet.to_string();
+        self
+    }
+
+    pub fn date(mut self, date: NaiveDate) -> Self {
+        self.date = Some(date);
+        self
+    }
+
+    /// Fails with [`Error::Validation`] if a required field is missing
+    pub fn build(self) -> Result<LessonRequest> {
+        let missing = |field: &str| Error::Validation(format!("lesson {} is required", field));
+        Ok(LessonRequest {
+            group_id: self.group_id.ok_or_else(|| missing("group_id"))?,
+            order: self.order.ok_or_else(|| missing("order"))?,
+            title: self.title.ok_or_else(|| missing("title"))?,
+            teacher: self.teacher,
+            cabinet: self.cabinet,
+            date: self
+                .date
+                .ok_or_else(|| missing("date"))?
+                .format(DATE_FORMAT)
+                .to_string(),
+        })
+    }
+}
+
+/// Lessons of a group to delete, by date range and optionally by order.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteLessonsRequest {
+    #[serde(rename = "groupId")]
+    pub group_id: u32,
+    /// First date of the range, inclusive
+    pub from: String,
+    /// Last date of the range, inclusive
+    pub to: String,
+    /// Only the lesson with this order; every lesson if `None`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<u32>,
+}
+
+impl DeleteLessonsRequest {
+    /// Deletes every lesson of the group between `from` and `to`
+    pub fn range(group_id: u32, from: NaiveDate, to: NaiveDate) -> Self {
+        Self {
+            group_id,
+            from: from.format(DATE_FORMAT).to_string(),
+            to: to.format(DATE_FORMAT).to_string(),
+            order: None,
+        }
+    }
+
+    /// Clears one date of the group
+    pub fn date(group_id: u32, date: NaiveDate) -> Self {
+        Self::range(group_id, date, date)
+    }
+
+    /// Deletes a single lesson
+    pub fn lesson(group_id: u32, date: NaiveDate, order: u32) -> Self {
+        Self {
+            order: Some(order),
+            ..Self::date(group_id, date)
+        }
+    }
+}
+
+/// Replaces every lesson of a group between two dates with `lessons`.
+///
+/// Dates in the range without lessons in the payload end up empty.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplaceLessonsRequest {
+    #[serde(rename = "groupId")]
+    pub group_id: u32,
+    pub from: String,
+    pub to: String,
+    pub lessons: Vec<LessonRequest>,
+}
+
+impl ReplaceLessonsRequest {
+    pub fn new(group_id: u32, from: NaiveDate, to: NaiveDate, lessons: Vec<LessonRequest>) -> Self {
+        Self {
+            group_id,
+            from: from.format(DATE_FORMAT).to_string(),
+            to: to.format(DATE_FORMAT).to_string(),
+            lessons,
+        }
+    }
+}
+
+/// Groups to remove from a campus, together with their lessons.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteGroupsRequest {
+    #[serde(rename = "campusId")]
+    pub campus_id: u32,
+    #[serde(rename = "studentGroupNames")]
+    pub student_group_names: Vec<String>,
+}
+
+/// Calls to remove by order, on every weekday.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeleteCallsRequest {
+    pub orders: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeleteResponse {
+    /// Number of removed records
+    #[serde(default)]
+    pub deleted: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplaceLessonsResponse {
+    #[serde(default)]
+    pub deleted: u32,
+    #[serde(default)]
+    pub added: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CreateParserResponse {
+    /// ID of the new parser, needed to inspect, rotate or delete it
+    #[serde(rename = "parserId")]
+    pub id: u32,
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddCampusesRequest {
+    pub campus_names: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RenameCampusRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RotateTokenResponse {
     pub token: String,
 }
+
+/// A parser as seen by administrators.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParserInfo {
+    #[serde(rename = "parserId")]
+    pub id: u32,
+    #[serde(rename = "collegeId")]
+    pub college_id: u32,
+    #[serde(rename = "collegeName")]
+    pub college_name: String,
+    /// Campuses the parser may upload to
+    #[serde(default)]
+    pub campuses: Vec<Campus>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Validate;
+
+    #[test]
+    fn test_call_round_trip() {
+        let call = Call {
+            call_id: 7,
+            weekday: Weekday::Friday,
+            begins: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            ends: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+            order: 1,
+        };
+        let request = UpdateCallsRequest::from(std::slice::from_ref(&call));
+
+        assert_eq!(request.calls[0].begins, "09:00:00");
+        assert_eq!(request.calls[0].ends, "10:30:00");
+        assert!(request.validate().is_ok());
+        assert_eq!(
+            serde_json::to_value(&request.calls[0]).unwrap()["weekday"],
+            5
+        );
+    }
+
+    #[test]
+    fn test_lessons_from_schedule_and_builder() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let schedule = Schedule {
+            group_id: 3,
+            date,
+            lessons: vec![Lesson {
+                title: "Math".to_string(),
+                cabinet: "101".to_string(),
+                teacher: "Smith".to_string(),
+                order: 2,
+                start_time: NaiveTime::from_hms_opt(10, 40, 0).unwrap(),
+                end_time: NaiveTime::from_hms_opt(12, 10, 0).unwrap(),
+            }],
+        };
+        let request = UpdateLessonsRequest::from(&schedule);
+        let built = LessonRequest::builder()
+            .group_id(3)
+            .order(2)
+            .title("Math")
+            .teacher("Smith")
+            .cabinet("101")
+            .date(date)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&request.lessons[0]).unwrap(),
+            serde_json::to_value(&built).unwrap()
+        );
+        assert!(matches!(
+            LessonRequest::builder().group_id(3).build(),
+            Err(Error::Validation(_))
+        ));
+    }
+}
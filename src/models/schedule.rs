@@ -1,8 +1,9 @@
 use super::Lesson;
 use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Schedule {
     #[serde(rename = "groupId")]
     pub group_id: u32,
@@ -10,3 +11,9 @@ pub struct Schedule {
     pub date: NaiveDate,
     pub lessons: Vec<Lesson>,
 }
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(crate::render::Renderer::default().schedule(self).trim_end())
+    }
+}
@@ -0,0 +1,376 @@
+use super::{
+    DeleteCallsRequest, DeleteGroupsRequest, DeleteLessonsRequest, ReplaceLessonsRequest,
+    UpdateCallsRequest, UpdateGroupsRequest, UpdateLessonsRequest,
+};
+use crate::error::{Error, Result};
+use chrono::{NaiveDate, NaiveTime};
+use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Wire format of `CallRequest` times
+pub const TIME_FORMAT: &str = "%H:%M:%S";
+/// Wire format of `LessonRequest` dates
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// A single problem found in a request payload.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Problem {
+    /// Path to the offending value, e.g. `calls[2].begins`
+    pub field: String,
+    pub message: String,
+}
+
+impl Problem {
+    fn new(field: String, message: impl Into<String>) -> Self {
+        Self {
+            field,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.field, self.message)
+    }
+}
+
+/// Client-side checks run before a payload is sent to the parser API.
+///
+/// # Examples
+///
+/// ```
+/// use osars::{UpdateGroupsRequest, Validate};
+///
+/// let request = UpdateGroupsRequest {
+///     campus_id: 1,
+///     student_group_names: vec!["A".to_string(), "A".to_string()],
+/// };
+/// assert_eq!(request.problems().len(), 1);
+/// assert!(request.validate().is_err());
+/// ```
+pub trait Validate {
+    /// Every problem in the payload, empty if it is valid
+    fn problems(&self) -> Vec<Problem>;
+
+    /// Fails with [`Error::InvalidRequest`] listing all problems
+    fn validate(&self) -> Result<()> {
+        let problems = self.problems();
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::InvalidRequest(problems))
+        }
+    }
+}
+
+impl Validate for UpdateGroupsRequest {
+    fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (i, name) in self.student_group_names.iter().enumerate() {
+            let field = format!("student_group_names[{}]", i);
+            if name.trim().is_empty() {
+                problems.push(Problem::new(field, "group name is empty"));
+            } else if !seen.insert(name.trim()) {
+                problems.push(Problem::new(
+                    field,
+                    format!("duplicate group name '{}'", name),
+                ));
+            }
+        }
+
+        problems
+    }
+}
+
+impl Validate for UpdateCallsRequest {
+    /// Weekdays need no check: `CallRequest.weekday` can only hold 1–7.
+    fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        let mut seen = HashSet::new();
+
+        for (i, call) in self.calls.iter().enumerate() {
+            let begins = parse_time(&call.begins, format!("calls[{}].begins", i), &mut problems);
+            let ends = parse_time(&call.ends, format!("calls[{}].ends", i), &mut problems);
+
+            if let (Some(begins), Some(ends)) = (begins, ends)
+                && begins >= ends
+            {
+                problems.push(Problem::new(
+                    format!("calls[{}]", i),
+                    format!("begins at {} but ends at {}", call.begins, call.ends),
+                ));
+            }
+
+            if !seen.insert((call.weekday, call.order)) {
+                problems.push(Problem::new(
+                    format!("calls[{}].order", i),
+                    format!("duplicate order {} on {}", call.order, call.weekday),
+                ));
+            }
+        }
+
+        problems
+    }
+}
+
+impl Validate for UpdateLessonsRequest {
+    fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        let mut seen: HashMap<(u32, &str, u32), usize> = HashMap::new();
+
+        for (i, lesson) in self.lessons.iter().enumerate() {
+            if lesson.title.trim().is_empty() {
+                problems.push(Problem::new(
+                    format!("lessons[{}].title", i),
+                    "title is empty",
+                ));
+            }
+
+            if NaiveDate::parse_from_str(&lesson.date, DATE_FORMAT).is_err() {
+                problems.push(Problem::new(
+                    format!("lessons[{}].date", i),
+                    format!("'{}' is not a date in {} format", lesson.date, DATE_FORMAT),
+                ));
+            }
+
+            let key = (lesson.group_id, lesson.date.as_str(), lesson.order);
+            match seen.entry(key) {
+                Entry::Occupied(first) => problems.push(Problem::new(
+                    format!("lessons[{}]", i),
+                    format!(
+                        "group {} already has order {} on {} (lessons[{}])",
+                        lesson.group_id,
+                        lesson.order,
+                        lesson.date,
+                        first.get()
+                    ),
+                )),
+                Entry::Vacant(entry) => {
+                    entry.insert(i);
+                }
+            }
+        }
+
+        problems
+    }
+}
+
+impl Validate for DeleteLessonsRequest {
+    fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        parse_range(&self.from, &self.to, &mut problems);
+        problems
+    }
+}
+
+impl Validate for ReplaceLessonsRequest {
+    fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        let range = parse_range(&self.from, &self.to, &mut problems);
+
+        for (i, lesson) in self.lessons.iter().enumerate() {
+            if lesson.group_id != self.group_id {
+                problems.push(Problem::new(
+                    format!("lessons[{}].group_id", i),
+                    format!(
+                        "lesson belongs to group {}, not {}",
+                        lesson.group_id, self.group_id
+                    ),
+                ));
+            }
+            if let (Some((from, to)), Ok(date)) =
+                (range, NaiveDate::parse_from_str(&lesson.date, DATE_FORMAT))
+                && !(from..=to).contains(&date)
+            {
+                problems.push(Problem::new(
+                    format!("lessons[{}].date", i),
+                    format!("{} is outside {}..={}", lesson.date, self.from, self.to),
+                ));
+            }
+        }
+
+        // Field paths already start with `lessons[..]`, as in the replace payload
+        let lessons = UpdateLessonsRequest {
+            lessons: self.lessons.clone(),
+        };
+        problems.extend(lessons.problems());
+        problems
+    }
+}
+
+impl Validate for DeleteGroupsRequest {
+    fn problems(&self) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        if self.student_group_names.is_empty() {
+            problems.push(Problem::new(
+                "student_group_names".to_string(),
+                "no groups to delete",
+            ));
+        }
+        for (i, name) in self.student_group_names.iter().enumerate() {
+            if name.trim().is_empty() {
+                problems.push(Problem::new(
+                    format!("student_group_names[{}]", i),
+                    "group name is empty",
+                ));
+            }
+        }
+        problems
+    }
+}
+
+impl Validate for DeleteCallsRequest {
+    fn problems(&self) -> Vec<Problem> {
+        if self.orders.is_empty() {
+            vec![Problem::new("orders".to_string(), "no calls to delete")]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+fn parse_range(
+    from: &str,
+    to: &str,
+    problems: &mut Vec<Problem>,
+) -> Option<(NaiveDate, NaiveDate)> {
+    let from_date = parse_date(from, "from".to_string(), problems);
+    let to_date = parse_date(to, "to".to_string(), problems);
+    let (from_date, to_date) = (from_date?, to_date?);
+
+    if from_date > to_date {
+        problems.push(Problem::new(
+            "to".to_string(),
+            format!("range ends on {} before it starts on {}", to, from),
+        ));
+        return None;
+    }
+    Some((from_date, to_date))
+}
+
+fn parse_date(value: &str, field: String, problems: &mut Vec<Problem>) -> Option<NaiveDate> {
+    match NaiveDate::parse_from_str(value, DATE_FORMAT) {
+        Ok(date) => Some(date),
+        Err(_) => {
+            problems.push(Problem::new(
+                field,
+                format!("'{}' is not a date in {} format", value, DATE_FORMAT),
+            ));
+            None
+        }
+    }
+}
+
+fn parse_time(value: &str, field: String, problems: &mut Vec<Problem>) -> Option<NaiveTime> {
+    match NaiveTime::parse_from_str(value, TIME_FORMAT) {
+        Ok(time) => Some(time),
+        Err(_) => {
+            problems.push(Problem::new(
+                field,
+                format!("'{}' is not a time in {} format", value, TIME_FORMAT),
+            ));
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{CallRequest, LessonRequest, Weekday};
+
+    fn call(weekday: Weekday, begins: &str, ends: &str, order: u32) -> CallRequest {
+        CallRequest {
+            weekday,
+            begins: begins.to_string(),
+            ends: ends.to_string(),
+            order,
+        }
+    }
+
+    fn lesson(group_id: u32, title: &str, date: &str, order: u32) -> LessonRequest {
+        LessonRequest {
+            group_id,
+            order,
+            title: title.to_string(),
+            teacher: "Smith".to_string(),
+            cabinet: "101".to_string(),
+            date: date.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_calls_collect_all_problems() {
+        let request = UpdateCallsRequest {
+            calls: vec![
+                call(Weekday::Monday, "09:00:00", "10:30:00", 1),
+                call(Weekday::Monday, "11:00:00", "10:30:00", 1),
+                call(Weekday::Tuesday, "9am", "10:30:00", 1),
+            ],
+        };
+        let fields: Vec<String> = request.problems().into_iter().map(|p| p.field).collect();
+
+        assert_eq!(
+            fields,
+            vec!["calls[1]", "calls[1].order", "calls[2].begins"]
+        );
+    }
+
+    #[test]
+    fn test_replace_checks_range_and_lessons() {
+        let request = ReplaceLessonsRequest {
+            group_id: 1,
+            from: "2024-01-15".to_string(),
+            to: "2024-01-16".to_string(),
+            lessons: vec![
+                lesson(1, "Math", "2024-01-15", 1),
+                lesson(2, "Math", "2024-01-16", 1),
+                lesson(1, "Math", "2024-01-17", 1),
+                lesson(1, "", "2024-01-16", 2),
+            ],
+        };
+        let fields: Vec<String> = request.problems().into_iter().map(|p| p.field).collect();
+        assert_eq!(
+            fields,
+            vec!["lessons[1].group_id", "lessons[2].date", "lessons[3].title"]
+        );
+
+        let backwards = DeleteLessonsRequest {
+            group_id: 1,
+            from: "2024-01-16".to_string(),
+            to: "2024-01-15".to_string(),
+            order: None,
+        };
+        assert_eq!(backwards.problems()[0].field, "to");
+    }
+
+    #[test]
+    fn test_lessons_and_error_message() {
+        let request = UpdateLessonsRequest {
+            lessons: vec![
+                lesson(1, "Math", "2024-01-15", 1),
+                lesson(1, " ", "2024-01-15", 1),
+                lesson(2, "Math", "15.01.2024", 1),
+                lesson(1, "Math", "2024-01-15", 1),
+            ],
+        };
+        let problems = request.problems();
+
+        assert_eq!(problems.len(), 4);
+        assert_eq!(problems[0].field, "lessons[1].title");
+        assert_eq!(problems[2].field, "lessons[2].date");
+        assert!(problems[3].message.ends_with("(lessons[0])"));
+
+        let error = request.validate().unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("lessons[1].title: title is empty; ")
+        );
+    }
+}
@@ -0,0 +1,84 @@
+pub mod webhook;
+pub mod writer;
+
+pub use webhook::WebhookSink;
+pub use writer::{FileSink, StdoutSink};
+
+use crate::error::Result;
+use crate::{Reminder, ScheduleChange};
+use futures::future::BoxFuture;
+use futures::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "logging")]
+use tracing::warn;
+
+/// An event produced by the watcher or the reminder engine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+pub enum Notification {
+    ScheduleChange(ScheduleChange),
+    Reminder(Reminder),
+}
+
+impl From<ScheduleChange> for Notification {
+    fn from(change: ScheduleChange) -> Self {
+        Notification::ScheduleChange(change)
+    }
+}
+
+impl From<Reminder> for Notification {
+    fn from(reminder: Reminder) -> Self {
+        Notification::Reminder(reminder)
+    }
+}
+
+/// Destination for notifications.
+///
+/// Implementations return boxed futures so that sinks can be combined as
+/// trait objects, e.g. `Vec<Box<dyn NotificationSink>>`.
+pub trait NotificationSink: Send + Sync {
+    /// Delivers a single notification
+    fn send<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>>;
+}
+
+impl<S: NotificationSink + ?Sized> NotificationSink for Box<S> {
+    fn send<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        (**self).send(notification)
+    }
+}
+
+/// Fans a notification out to every sink, failing if any of them fails.
+impl<S: NotificationSink> NotificationSink for Vec<S> {
+    fn send<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let results =
+                futures::future::join_all(self.iter().map(|s| s.send(notification))).await;
+            results.into_iter().collect::<Result<Vec<()>>>().map(|_| ())
+        })
+    }
+}
+
+/// Routes every item of a watcher or reminder stream into a sink.
+///
+/// Errors yielded by the stream are skipped, since both the watcher and the
+/// reminder engine recover from them on their own. Returns when the stream
+/// ends or the sink fails.
+pub async fn forward<S, T, N>(stream: S, sink: &N) -> Result<()>
+where
+    S: Stream<Item = Result<T>>,
+    T: Into<Notification>,
+    N: NotificationSink + ?Sized,
+{
+    let mut stream = std::pin::pin!(stream);
+    while let Some(item) = stream.next().await {
+        match item {
+            Ok(item) => sink.send(&item.into()).await?,
+            Err(_e) => {
+                #[cfg(feature = "logging")]
+                warn!("Skipping failed stream item: {}", _e);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use super::{Notification, NotificationSink};
+use crate::error::{Error, Result};
+use futures::future::BoxFuture;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::Duration;
+
+#[cfg(feature = "logging")]
+use tracing::{debug, warn};
+
+/// Header carrying the HMAC-SHA256 signature of the request body
+pub const SIGNATURE_HEADER: &str = "X-Osars-Signature";
+
+/// Posts notifications as JSON to an HTTP endpoint.
+///
+/// When a secret is configured, every request carries a
+/// `X-Osars-Signature: sha256=<hex>` header computed over the raw body, so
+/// the receiver can verify the sender. Network errors, `429` and `5xx`
+/// responses are retried with exponential backoff.
+///
+/// # Examples
+///
+/// ```
+/// use osars::notify::WebhookSink;
+/// use std::time::Duration;
+///
+/// let sink = WebhookSink::new("https://hooks.example.com/schedule")
+///     .secret("s3cr3t")
+///     .retries(5)
+///     .retry_delay(Duration::from_secs(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WebhookSink {
+    url: String,
+    http_client: reqwest::Client,
+    secret: Option<Vec<u8>>,
+    retries: u32,
+    retry_delay: Duration,
+}
+
+impl WebhookSink {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            http_client: reqwest::Client::new(),
+            secret: None,
+            retries: 3,
+            retry_delay: Duration::from_millis(500),
+        }
+    }
+
+    /// Uses a custom HTTP client
+    pub fn with_client(mut self, http_client: reqwest::Client) -> Self {
+        self.http_client = http_client;
+        self
+    }
+
+    /// Signs request bodies with the given secret
+    pub fn secret(mut self, secret: &str) -> Self {
+        self.secret = Some(secret.as_bytes().to_vec());
+        self
+    }
+
+    /// Sets how many times a failed delivery is retried
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry; later retries double it
+    pub fn retry_delay(mut self, retry_delay: Duration) -> Self {
+        self.retry_delay = retry_delay;
+        self
+    }
+
+    async fn deliver(&self, body: &[u8]) -> Result<()> {
+        let mut request = self
+            .http_client
+            .post(&self.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_vec());
+
+        if let Some(secret) = &self.secret {
+            request = request.header(SIGNATURE_HEADER, signature(secret, body));
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            let raw_body = response.text().await.unwrap_or_default();
+            Err(Error::from_response(status.as_u16(), raw_body))
+        }
+    }
+}
+
+impl NotificationSink for WebhookSink {
+    fn send<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let body = serde_json::to_vec(notification)?;
+            let mut delay = self.retry_delay;
+            let mut attempt = 0;
+
+            loop {
+                match self.deliver(&body).await {
+                    Ok(()) => {
+                        #[cfg(feature = "logging")]
+                        debug!("Delivered webhook to {}", self.url);
+                        return Ok(());
+                    }
+                    Err(e) if attempt < self.retries && e.is_retryable() => {
+                        #[cfg(feature = "logging")]
+                        warn!("Webhook delivery to {} failed, retrying: {}", self.url, e);
+                        tokio::time::sleep(delay).await;
+                        delay = delay.saturating_mul(2);
+                        attempt += 1;
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        })
+    }
+}
+
+/// Computes the signature header value for `body`.
+pub fn signature(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any size");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Lesson, Reminder};
+    use chrono::{DateTime, NaiveDate, NaiveTime};
+    use mockito::{Matcher, Server};
+
+    fn notification() -> Notification {
+        let starts_at = DateTime::parse_from_rfc3339("2024-01-15T09:00:00+05:00").unwrap();
+        Notification::Reminder(Reminder {
+            group_id: 1,
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            lesson: Lesson {
+                title: "Math".to_string(),
+                cabinet: "101".to_string(),
+                teacher: "Smith".to_string(),
+                order: 1,
+                start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                end_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+            },
+            lead: Duration::from_secs(600),
+            starts_at,
+            fire_at: starts_at.to_utc(),
+        })
+    }
+
+    #[test]
+    fn test_signature_known_value() {
+        // RFC 4231 test case 2
+        assert_eq!(
+            signature(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_webhook_signs_and_retries() {
+        let mut server = Server::new_async().await;
+        let notification = notification();
+        let body = serde_json::to_vec(&notification).unwrap();
+
+        let failing = server
+            .mock("POST", "/hook")
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        let ok = server
+            .mock("POST", "/hook")
+            .match_header(SIGNATURE_HEADER, signature(b"secret", &body).as_str())
+            .match_body(Matcher::Exact(String::from_utf8(body.clone()).unwrap()))
+            .with_status(204)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let sink = WebhookSink::new(&format!("{}/hook", server.url()))
+            .secret("secret")
+            .retry_delay(Duration::from_millis(1));
+
+        sink.send(&notification).await.unwrap();
+
+        failing.assert_async().await;
+        ok.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_webhook_gives_up_on_client_error() {
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("POST", "/hook")
+            .with_status(400)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let sink = WebhookSink::new(&format!("{}/hook", server.url()))
+            .retry_delay(Duration::from_millis(1));
+        let result = sink.send(&notification()).await;
+
+        mock.assert_async().await;
+        assert!(matches!(
+            result,
+            Err(Error::Api {
+                status_code: 400,
+                ..
+            })
+        ));
+    }
+}
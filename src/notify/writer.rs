@@ -0,0 +1,89 @@
+use super::{Notification, NotificationSink};
+use crate::error::Result;
+use futures::future::BoxFuture;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Prints notifications to stdout as JSON lines.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StdoutSink;
+
+impl NotificationSink for StdoutSink {
+    fn send<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let line = serde_json::to_string(notification)?;
+            writeln!(std::io::stdout().lock(), "{}", line)?;
+            Ok(())
+        })
+    }
+}
+
+/// Appends notifications to a file as JSON lines.
+#[derive(Debug)]
+pub struct FileSink {
+    file: Mutex<File>,
+}
+
+impl FileSink {
+    /// Opens `path` for appending, creating it if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl NotificationSink for FileSink {
+    fn send<'a>(&'a self, notification: &'a Notification) -> BoxFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let line = serde_json::to_string(notification)?;
+            let mut file = self.file.lock().unwrap();
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ScheduleChange;
+    use crate::models::Lesson;
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[tokio::test]
+    async fn test_file_sink_appends_json_lines() {
+        let path = std::env::temp_dir().join(format!("osars-sink-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let notification = Notification::from(ScheduleChange::Added {
+            group_id: 1,
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            lesson: Lesson {
+                title: "Math".to_string(),
+                cabinet: "101".to_string(),
+                teacher: "Smith".to_string(),
+                order: 1,
+                start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                end_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+            },
+        });
+
+        let sink = FileSink::open(&path).unwrap();
+        sink.send(&notification).await.unwrap();
+        sink.send(&notification).await.unwrap();
+
+        let raw = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<_> = raw.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let parsed: Notification = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(parsed, notification);
+        assert!(lines[0].contains("\"type\":\"schedule_change\""));
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,296 @@
+use crate::{Call, Lesson, Schedule, Weekday};
+use chrono::{NaiveDate, NaiveTime, TimeDelta};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Length of an academic hour in minutes
+pub const ACADEMIC_HOUR_MINUTES: i64 = 45;
+
+/// One lesson period of a day, with or without lessons in it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Period {
+    pub order: u32,
+    /// Bell time if the period has a call, otherwise the lessons' own times
+    pub begins: NaiveTime,
+    pub ends: NaiveTime,
+    pub call: Option<Call>,
+    pub lessons: Vec<Lesson>,
+}
+
+impl Period {
+    /// Whether no lesson takes place in this period
+    pub fn is_empty(&self) -> bool {
+        self.lessons.is_empty()
+    }
+
+    pub fn duration(&self) -> TimeDelta {
+        self.ends - self.begins
+    }
+}
+
+/// Time between two consecutive periods.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Break {
+    /// Order of the period before the break
+    pub after: u32,
+    /// Order of the period after the break
+    pub before: u32,
+    pub begins: NaiveTime,
+    pub ends: NaiveTime,
+}
+
+impl Break {
+    pub fn duration(&self) -> TimeDelta {
+        self.ends - self.begins
+    }
+}
+
+/// A lesson whose times differ from the bell schedule for its order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Mismatch {
+    pub lesson: Lesson,
+    pub call: Call,
+}
+
+/// A day's lessons laid over the college's bell schedule.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use osars::Schedule;
+/// use osars::plan::DayPlan;
+///
+/// let schedule = Schedule {
+///     group_id: 1,
+///     date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+///     lessons: vec![],
+/// };
+/// let plan = DayPlan::new(&schedule, &[]);
+/// assert!(plan.first_lesson().is_none());
+/// assert_eq!(plan.academic_hours(), 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayPlan {
+    pub group_id: u32,
+    pub date: NaiveDate,
+    pub weekday: Weekday,
+    /// All periods of the day ordered by `order`
+    pub periods: Vec<Period>,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl DayPlan {
+    /// Builds a plan from a schedule and the college's calls for any weekday.
+    pub fn new(schedule: &Schedule, calls: &[Call]) -> Self {
+        let weekday = Weekday::of(schedule.date);
+        let mut periods: BTreeMap<u32, (Option<&Call>, Vec<&Lesson>)> = BTreeMap::new();
+
+        for call in calls.iter().filter(|c| c.weekday == weekday) {
+            periods.entry(call.order).or_default().0 = Some(call);
+        }
+        for lesson in &schedule.lessons {
+            periods.entry(lesson.order).or_default().1.push(lesson);
+        }
+
+        let mut mismatches = Vec::new();
+        let periods = periods
+            .into_iter()
+            .map(|(order, (call, lessons))| {
+                let (begins, ends) = match call {
+                    Some(call) => (call.begins, call.ends),
+                    None => (
+                        lessons
+                            .iter()
+                            .map(|l| l.start_time)
+                            .min()
+                            .unwrap_or_default(),
+                        lessons.iter().map(|l| l.end_time).max().unwrap_or_default(),
+                    ),
+                };
+
+                if let Some(call) = call {
+                    mismatches.extend(
+                        lessons
+                            .iter()
+                            .filter(|l| l.start_time != call.begins || l.end_time != call.ends)
+                            .map(|l| Mismatch {
+                                lesson: (*l).clone(),
+                                call: call.clone(),
+                            }),
+                    );
+                }
+
+                Period {
+                    order,
+                    begins,
+                    ends,
+                    call: call.cloned(),
+                    lessons: lessons.into_iter().cloned().collect(),
+                }
+            })
+            .collect();
+
+        Self {
+            group_id: schedule.group_id,
+            date: schedule.date,
+            weekday,
+            periods,
+            mismatches,
+        }
+    }
+
+    fn occupied(&self) -> impl Iterator<Item = &Period> {
+        self.periods.iter().filter(|p| !p.is_empty())
+    }
+
+    /// Period holding the first lesson of the day
+    pub fn first(&self) -> Option<&Period> {
+        self.occupied().next()
+    }
+
+    /// Period holding the last lesson of the day
+    pub fn last(&self) -> Option<&Period> {
+        self.occupied().last()
+    }
+
+    pub fn first_lesson(&self) -> Option<&Lesson> {
+        self.first().and_then(|p| p.lessons.first())
+    }
+
+    pub fn last_lesson(&self) -> Option<&Lesson> {
+        self.last().and_then(|p| p.lessons.last())
+    }
+
+    /// Empty periods between the first and the last lesson
+    pub fn windows(&self) -> Vec<&Period> {
+        let (Some(first), Some(last)) = (self.first(), self.last()) else {
+            return Vec::new();
+        };
+        self.periods
+            .iter()
+            .filter(|p| p.is_empty() && p.order > first.order && p.order < last.order)
+            .collect()
+    }
+
+    /// Breaks between consecutive periods from the first to the last lesson
+    pub fn breaks(&self) -> Vec<Break> {
+        let (Some(first), Some(last)) = (self.first(), self.last()) else {
+            return Vec::new();
+        };
+        let span: Vec<&Period> = self
+            .periods
+            .iter()
+            .filter(|p| p.order >= first.order && p.order <= last.order)
+            .collect();
+
+        span.windows(2)
+            .filter(|pair| pair[1].begins > pair[0].ends)
+            .map(|pair| Break {
+                after: pair[0].order,
+                before: pair[1].order,
+                begins: pair[0].ends,
+                ends: pair[1].begins,
+            })
+            .collect()
+    }
+
+    /// Time spent in periods with lessons, in academic hours
+    pub fn academic_hours(&self) -> f64 {
+        let minutes: i64 = self.occupied().map(|p| p.duration().num_minutes()).sum();
+        minutes as f64 / ACADEMIC_HOUR_MINUTES as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(hour: u32, minute: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    fn call(weekday: Weekday, order: u32, begins: NaiveTime, ends: NaiveTime) -> Call {
+        Call {
+            call_id: order,
+            weekday,
+            begins,
+            ends,
+            order,
+        }
+    }
+
+    fn lesson(order: u32, start_time: NaiveTime, end_time: NaiveTime) -> Lesson {
+        Lesson {
+            title: format!("Lesson {}", order),
+            cabinet: "101".to_string(),
+            teacher: "Smith".to_string(),
+            order,
+            start_time,
+            end_time,
+        }
+    }
+
+    fn calls() -> Vec<Call> {
+        vec![
+            call(Weekday::Monday, 1, time(8, 30), time(10, 0)),
+            call(Weekday::Monday, 2, time(10, 10), time(11, 40)),
+            call(Weekday::Monday, 3, time(12, 10), time(13, 40)),
+            call(Weekday::Monday, 4, time(13, 50), time(15, 20)),
+            call(Weekday::Monday, 5, time(15, 30), time(17, 0)),
+            call(Weekday::Tuesday, 1, time(9, 0), time(10, 30)),
+        ]
+    }
+
+    #[test]
+    fn test_windows_breaks_and_hours() {
+        let schedule = Schedule {
+            group_id: 1,
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            lessons: vec![
+                lesson(4, time(13, 50), time(15, 20)),
+                lesson(2, time(10, 10), time(11, 40)),
+            ],
+        };
+        let plan = DayPlan::new(&schedule, &calls());
+
+        assert_eq!(plan.weekday, Weekday::Monday);
+        assert_eq!(plan.periods.len(), 5);
+        assert_eq!(plan.first_lesson().unwrap().order, 2);
+        assert_eq!(plan.last_lesson().unwrap().order, 4);
+
+        let windows: Vec<u32> = plan.windows().iter().map(|p| p.order).collect();
+        assert_eq!(windows, vec![3]);
+
+        let breaks = plan.breaks();
+        assert_eq!(breaks.len(), 2);
+        assert_eq!((breaks[0].after, breaks[0].before), (2, 3));
+        assert_eq!(breaks[0].duration(), TimeDelta::minutes(30));
+        assert_eq!(breaks[1].duration(), TimeDelta::minutes(10));
+
+        assert_eq!(plan.academic_hours(), 4.0);
+        assert!(plan.mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_mismatch_and_period_without_call() {
+        let schedule = Schedule {
+            group_id: 1,
+            date: NaiveDate::from_ymd_opt(2024, 1, 16).unwrap(),
+            lessons: vec![
+                lesson(1, time(9, 0), time(10, 20)),
+                lesson(2, time(10, 40), time(12, 10)),
+            ],
+        };
+        let plan = DayPlan::new(&schedule, &calls());
+
+        assert_eq!(plan.mismatches.len(), 1);
+        assert_eq!(plan.mismatches[0].lesson.order, 1);
+        assert_eq!(plan.mismatches[0].call.ends, time(10, 30));
+
+        let second = &plan.periods[1];
+        assert!(second.call.is_none());
+        assert_eq!((second.begins, second.ends), (time(10, 40), time(12, 10)));
+        assert_eq!(plan.breaks()[0].duration(), TimeDelta::minutes(10));
+    }
+}
@@ -0,0 +1,314 @@
+use crate::error::{Error, Result};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+#[cfg(feature = "logging")]
+use tracing::{debug, warn};
+
+/// Longest single wait in [`RateLimiter::acquire`]; the bucket is checked
+/// again afterwards
+const MAX_WAIT: Duration = Duration::from_secs(3600);
+
+/// Sustained rate and burst size of one token bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    per_second: f64,
+    burst: u32,
+}
+
+impl RateLimit {
+    /// Allows `per_second` requests on average and `burst` at once.
+    ///
+    /// Fails with [`Error::Validation`] unless `per_second` is finite and
+    /// positive; a `burst` of zero is raised to one.
+    pub fn new(per_second: f64, burst: u32) -> Result<Self> {
+        if !per_second.is_finite() || per_second <= 0.0 {
+            return Err(Error::Validation(format!(
+                "rate limit must be a positive number of requests per second, got {}",
+                per_second
+            )));
+        }
+        Ok(Self {
+            per_second,
+            burst: burst.max(1),
+        })
+    }
+
+    pub fn per_second(&self) -> f64 {
+        self.per_second
+    }
+
+    /// Requests that may be sent at once after a quiet period
+    pub fn burst(&self) -> u32 {
+        self.burst
+    }
+}
+
+/// Which bucket a request draws from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Budget {
+    /// Public schedule queries and admin endpoints
+    Read,
+    /// Everything under `/parser`
+    Parser,
+}
+
+impl Budget {
+    pub fn for_path(path: &str) -> Self {
+        if path.starts_with("/parser") {
+            Budget::Parser
+        } else {
+            Budget::Read
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    /// `None` if the budget is not throttled; `Retry-After` pauses still apply
+    limit: Option<RateLimit>,
+    tokens: f64,
+    updated: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(limit: Option<RateLimit>) -> Self {
+        Self {
+            limit,
+            tokens: limit.map_or(0.0, |l| f64::from(l.burst)),
+            updated: Instant::now(),
+            paused_until: None,
+        }
+    }
+
+    /// Takes a token, or returns how long to wait before trying again
+    fn try_take(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(until) = self.paused_until {
+            if now < until {
+                return Some(until - now);
+            }
+            self.paused_until = None;
+        }
+
+        let limit = self.limit?;
+        let refill = now.duration_since(self.updated).as_secs_f64() * limit.per_second;
+        self.tokens = (self.tokens + refill).min(f64::from(limit.burst));
+        self.updated = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            None
+        } else {
+            let wait = Duration::try_from_secs_f64((1.0 - self.tokens) / limit.per_second)
+                .unwrap_or(MAX_WAIT);
+            Some(wait.min(MAX_WAIT))
+        }
+    }
+
+    fn pause_until(&mut self, until: Instant) {
+        self.paused_until = Some(
+            self.paused_until
+                .map_or(until, |current| current.max(until)),
+        );
+    }
+}
+
+/// Token-bucket limits applied by [`Client`](crate::Client) before each request.
+///
+/// Read and parser endpoints have separate budgets; a budget without a limit
+/// is not throttled. Clones share their buckets, so every clone of a client
+/// draws from the same budget. A `429` response with `Retry-After` pauses
+/// the budget until the server allows requests again, whether or not it has
+/// a limit.
+///
+/// # Examples
+///
+/// ```
+/// use osars::{Client, RateLimit, RateLimiter};
+///
+/// let client = Client::new("https://api.thisishyum.ru/schedule_api/tyumen").with_rate_limiter(
+///     RateLimiter::new()
+///         .read(RateLimit::new(5.0, 10)?)
+///         .parser(RateLimit::new(1.0, 2)?),
+/// );
+/// # Ok::<(), osars::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    read: Arc<Mutex<Bucket>>,
+    parser: Arc<Mutex<Bucket>>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self {
+            read: Arc::new(Mutex::new(Bucket::new(None))),
+            parser: Arc::new(Mutex::new(Bucket::new(None))),
+        }
+    }
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Limits public and admin requests
+    pub fn read(mut self, limit: RateLimit) -> Self {
+        self.read = Arc::new(Mutex::new(Bucket::new(Some(limit))));
+        self
+    }
+
+    /// Limits requests to parser endpoints
+    pub fn parser(mut self, limit: RateLimit) -> Self {
+        self.parser = Arc::new(Mutex::new(Bucket::new(Some(limit))));
+        self
+    }
+
+    fn bucket(&self, budget: Budget) -> &Mutex<Bucket> {
+        match budget {
+            Budget::Read => &self.read,
+            Budget::Parser => &self.parser,
+        }
+    }
+
+    /// Waits until `budget` allows another request
+    pub async fn acquire(&self, budget: Budget) {
+        let bucket = self.bucket(budget);
+
+        loop {
+            let wait = bucket
+                .lock()
+                .expect("rate limiter lock poisoned")
+                .try_take(Instant::now());
+            match wait {
+                None => return,
+                Some(wait) => {
+                    #[cfg(feature = "logging")]
+                    debug!("Rate limited ({:?} budget), waiting {:?}", budget, wait);
+                    tokio::time::sleep(wait).await;
+                }
+            }
+        }
+    }
+
+    /// Holds back every request of `budget` for `delay`
+    pub fn pause(&self, budget: Budget, delay: Duration) {
+        #[cfg(feature = "logging")]
+        warn!(
+            "Server asked to retry after {:?} ({:?} budget)",
+            delay, budget
+        );
+
+        self.bucket(budget)
+            .lock()
+            .expect("rate limiter lock poisoned")
+            .pause_until(Instant::now() + delay.min(MAX_WAIT));
+    }
+}
+
+/// Parses `Retry-After` given either in seconds or as an HTTP date
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    (at.with_timezone(&chrono::Utc) - chrono::Utc::now())
+        .to_std()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use mockito::Server;
+
+    #[test]
+    fn test_bucket_refills_at_rate() {
+        let mut bucket = Bucket::new(Some(RateLimit::new(10.0, 2).unwrap()));
+        let start = bucket.updated;
+
+        assert_eq!(bucket.try_take(start), None);
+        assert_eq!(bucket.try_take(start), None);
+        let wait = bucket.try_take(start).unwrap();
+        assert_eq!(wait, Duration::from_millis(100));
+
+        assert_eq!(bucket.try_take(start + wait), None);
+
+        bucket.pause_until(start + Duration::from_secs(5));
+        let later = start + Duration::from_secs(1);
+        assert_eq!(bucket.try_take(later), Some(Duration::from_secs(4)));
+    }
+
+    #[tokio::test]
+    async fn test_clones_share_budget_and_respect_retry_after() {
+        let mut server = Server::new_async().await;
+        let limited = server
+            .mock("GET", "/colleges")
+            .with_status(429)
+            .with_header("retry-after", "1")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url())
+            .with_rate_limiter(RateLimiter::new().read(RateLimit::new(1000.0, 1).unwrap()));
+        let clone = client.clone();
+
+        assert!(client.colleges().send().await.is_err());
+        limited.assert_async().await;
+        limited.remove_async().await;
+        server
+            .mock("GET", "/colleges")
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let started = Instant::now();
+        clone.colleges().send().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(900));
+    }
+
+    #[test]
+    fn test_rejects_invalid_rates() {
+        for rate in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(RateLimit::new(rate, 1), Err(Error::Validation(_))));
+        }
+
+        let mut bucket = Bucket::new(Some(RateLimit::new(f64::MIN_POSITIVE, 1).unwrap()));
+        let start = bucket.updated;
+        assert_eq!(bucket.try_take(start), None);
+        assert_eq!(bucket.try_take(start), Some(MAX_WAIT));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_pauses_unlimited_budget() {
+        let mut server = Server::new_async().await;
+        let limited = server
+            .mock("GET", "/colleges")
+            .with_status(429)
+            .with_header("retry-after", "1")
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        assert!(client.colleges().send().await.is_err());
+        limited.assert_async().await;
+        limited.remove_async().await;
+        server
+            .mock("GET", "/colleges")
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let started = Instant::now();
+        client.clone().colleges().send().await.unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(900));
+    }
+}
@@ -0,0 +1,490 @@
+use crate::clock::{Clock, SystemClock};
+use crate::error::Result;
+use crate::{Client, Lesson, Schedule};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, TimeDelta, TimeZone, Utc};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
+
+#[cfg(feature = "logging")]
+use tracing::{debug, warn};
+
+/// Request to be reminded about every lesson of a group ahead of time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Subscription {
+    /// ID of the student group
+    pub group_id: u32,
+    /// How long before the lesson starts the reminder fires
+    pub lead: Duration,
+}
+
+/// A reminder that is due for a lesson.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reminder {
+    /// ID of the student group
+    pub group_id: u32,
+    /// Date of the lesson
+    pub date: NaiveDate,
+    /// The upcoming lesson
+    pub lesson: Lesson,
+    /// Lead time of the subscription that produced this reminder
+    pub lead: Duration,
+    /// Moment the lesson starts, with the engine timezone's offset on that date
+    pub starts_at: DateTime<FixedOffset>,
+    /// Moment the reminder was scheduled for
+    pub fire_at: DateTime<Utc>,
+}
+
+/// Fires reminders ahead of lessons for subscribed groups.
+///
+/// Schedules for the current and the next day are reloaded through the
+/// [`Client`] every refresh interval, so lessons moved or cancelled during
+/// the day are picked up before their reminder is due.
+///
+/// Lesson times are interpreted in the engine's [`TimeZone`], [`Local`] by
+/// default. The offset is looked up for every lesson date, so reminders stay
+/// correct across daylight saving changes.
+///
+/// # Examples
+///
+/// ```no_run
+/// use chrono::FixedOffset;
+/// use futures::StreamExt;
+/// use osars::Client;
+/// use std::time::Duration;
+///
+/// # async fn run() -> osars::Result<()> {
+/// let client = Client::new("https://api.example.com");
+/// let mut reminders = Box::pin(
+///     client
+///         .reminders()
+///         .subscribe(101, Duration::from_secs(600))
+///         .timezone(FixedOffset::east_opt(5 * 3600).unwrap())
+///         .stream(),
+/// );
+///
+/// while let Some(reminder) = reminders.next().await {
+///     let reminder = reminder?;
+///     println!("{} starts at {}", reminder.lesson.title, reminder.starts_at);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReminderEngine<C: Clock = SystemClock, Tz: TimeZone = Local> {
+    client: Client,
+    clock: C,
+    subscriptions: Vec<Subscription>,
+    timezone: Tz,
+    refresh_interval: Duration,
+}
+
+impl ReminderEngine<SystemClock, Local> {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            clock: SystemClock,
+            subscriptions: Vec::new(),
+            timezone: Local,
+            refresh_interval: Duration::from_secs(600),
+        }
+    }
+}
+
+impl<C: Clock, Tz: TimeZone> ReminderEngine<C, Tz> {
+    /// Adds a subscription for a group with the given lead time
+    pub fn subscribe(mut self, group_id: u32, lead: Duration) -> Self {
+        self.subscriptions.push(Subscription { group_id, lead });
+        self
+    }
+
+    /// Sets the timezone lesson times are expressed in (defaults to [`Local`])
+    pub fn timezone<T: TimeZone>(self, timezone: T) -> ReminderEngine<C, T> {
+        ReminderEngine {
+            client: self.client,
+            clock: self.clock,
+            subscriptions: self.subscriptions,
+            timezone,
+            refresh_interval: self.refresh_interval,
+        }
+    }
+
+    /// Sets how often schedules are reloaded
+    pub fn refresh_interval(mut self, refresh_interval: Duration) -> Self {
+        self.refresh_interval = refresh_interval;
+        self
+    }
+
+    /// Replaces the clock used to schedule reminders
+    pub fn with_clock<T: Clock>(self, clock: T) -> ReminderEngine<T, Tz> {
+        ReminderEngine {
+            client: self.client,
+            clock,
+            subscriptions: self.subscriptions,
+            timezone: self.timezone,
+            refresh_interval: self.refresh_interval,
+        }
+    }
+
+    /// Starts the engine and returns a stream of due reminders.
+    ///
+    /// Reminders whose moment has already passed when the schedule is loaded
+    /// are emitted immediately as long as the lesson has not started yet.
+    /// Load errors are yielded as `Err` items and retried on the next refresh.
+    pub fn stream(self) -> impl Stream<Item = Result<Reminder>> {
+        let engine = ReminderLoop {
+            engine: self,
+            schedules: HashMap::new(),
+            fired: HashSet::new(),
+            pending: VecDeque::new(),
+            next_refresh: None,
+        };
+
+        futures::stream::unfold(engine, |mut engine| async move {
+            let item = engine.next().await;
+            Some((item, engine))
+        })
+    }
+}
+
+/// A lesson slot: renaming or moving a lesson within its slot does not fire again
+type FiredKey = (u32, NaiveDate, u32, Duration);
+
+struct ReminderLoop<C: Clock, Tz: TimeZone> {
+    engine: ReminderEngine<C, Tz>,
+    schedules: HashMap<u32, Vec<Schedule>>,
+    fired: HashSet<FiredKey>,
+    pending: VecDeque<Result<Reminder>>,
+    next_refresh: Option<DateTime<Utc>>,
+}
+
+impl<C: Clock, Tz: TimeZone> ReminderLoop<C, Tz> {
+    async fn next(&mut self) -> Result<Reminder> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return item;
+            }
+
+            let now = self.engine.clock.now();
+            if self.next_refresh.is_none_or(|at| now >= at) {
+                self.refresh(now).await;
+                let interval =
+                    TimeDelta::from_std(self.engine.refresh_interval).unwrap_or(TimeDelta::days(1));
+                self.next_refresh = Some(now + interval);
+                continue;
+            }
+
+            let mut wake = self.next_refresh.unwrap_or(now);
+            for (fire_at, reminder) in self.upcoming() {
+                if fire_at <= now {
+                    self.fired.insert(fired_key(&reminder));
+                    self.pending.push_back(Ok(reminder));
+                } else {
+                    wake = wake.min(fire_at);
+                }
+            }
+
+            if self.pending.is_empty() {
+                self.engine.clock.sleep_until(wake).await;
+            }
+        }
+    }
+
+    async fn refresh(&mut self, now: DateTime<Utc>) {
+        let today = now.with_timezone(&self.engine.timezone).date_naive();
+        let dates = [today, today.succ_opt().unwrap_or(today)];
+
+        let group_ids: HashSet<u32> = self
+            .engine
+            .subscriptions
+            .iter()
+            .map(|s| s.group_id)
+            .collect();
+
+        for group_id in group_ids {
+            let mut schedules = Vec::new();
+            let mut failed = false;
+
+            for date in dates {
+                let date = date.format("%Y-%m-%d").to_string();
+                match self
+                    .engine
+                    .client
+                    .schedule(group_id)
+                    .date(&date)
+                    .send()
+                    .await
+                {
+                    Ok(fetched) => schedules.extend(fetched),
+                    Err(e) => {
+                        #[cfg(feature = "logging")]
+                        warn!("Failed to load schedule for group {}: {}", group_id, e);
+                        self.pending.push_back(Err(e));
+                        failed = true;
+                        break;
+                    }
+                }
+            }
+
+            // Keep the previous schedule on failure rather than dropping reminders
+            if !failed {
+                #[cfg(feature = "logging")]
+                debug!("Loaded {} days for group {}", schedules.len(), group_id);
+                self.schedules.insert(group_id, schedules);
+            }
+        }
+
+        self.fired
+            .retain(|(_, date, ..)| *date >= today.pred_opt().unwrap_or(today));
+    }
+
+    fn upcoming(&self) -> Vec<(DateTime<Utc>, Reminder)> {
+        let now = self.engine.clock.now();
+        let mut upcoming = Vec::new();
+
+        for subscription in &self.engine.subscriptions {
+            let Some(schedules) = self.schedules.get(&subscription.group_id) else {
+                continue;
+            };
+            let Ok(lead) = TimeDelta::from_std(subscription.lead) else {
+                continue;
+            };
+
+            for schedule in schedules {
+                for lesson in &schedule.lessons {
+                    let Some(starts_at) = self
+                        .engine
+                        .timezone
+                        .from_local_datetime(&schedule.date.and_time(lesson.start_time))
+                        .earliest()
+                        .map(|starts_at| starts_at.fixed_offset())
+                    else {
+                        continue;
+                    };
+
+                    if starts_at <= now {
+                        continue;
+                    }
+
+                    let fire_at = (starts_at - lead).with_timezone(&Utc);
+                    let reminder = Reminder {
+                        group_id: subscription.group_id,
+                        date: schedule.date,
+                        lesson: lesson.clone(),
+                        lead: subscription.lead,
+                        starts_at,
+                        fire_at,
+                    };
+
+                    if !self.fired.contains(&fired_key(&reminder)) {
+                        upcoming.push((fire_at, reminder));
+                    }
+                }
+            }
+        }
+
+        upcoming.sort_by_key(|(fire_at, _)| *fire_at);
+        upcoming
+    }
+}
+
+fn fired_key(reminder: &Reminder) -> FiredKey {
+    (
+        reminder.group_id,
+        reminder.date,
+        reminder.lesson.order,
+        reminder.lead,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::clock::test_tz::Dst;
+    use chrono::NaiveTime;
+    use futures::StreamExt;
+    use mockito::{Matcher, Server};
+
+    fn schedule_body(date: &str, title: &str, start: &str) -> String {
+        serde_json::json!([{
+            "groupId": 1,
+            "date": date,
+            "lessons": [{
+                "title": title,
+                "cabinet": "101",
+                "teacher": "Smith",
+                "order": 1,
+                "startTime": start,
+                "endTime": "23:59:00"
+            }]
+        }])
+        .to_string()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[tokio::test]
+    async fn test_reminder_fires_across_midnight() {
+        let mut server = Server::new_async().await;
+        let _today = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-01-15".into()))
+            .with_body("[]")
+            .create_async()
+            .await;
+        let _tomorrow = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-01-16".into()))
+            .with_body(schedule_body("2024-01-16", "Math", "00:05:00"))
+            .create_async()
+            .await;
+
+        // 23:40 local time in UTC+5
+        let clock = ManualClock::new(utc("2024-01-15T23:40:00+05:00"));
+        let client = Client::new(&server.url());
+        let mut reminders = Box::pin(
+            client
+                .reminders()
+                .subscribe(1, Duration::from_secs(600))
+                .timezone(FixedOffset::east_opt(5 * 3600).unwrap())
+                .refresh_interval(Duration::from_secs(3600))
+                .with_clock(clock.clone())
+                .stream(),
+        );
+
+        let first = tokio::time::timeout(Duration::from_millis(100), reminders.next()).await;
+        assert!(first.is_err(), "reminder fired too early");
+
+        clock.advance(TimeDelta::minutes(15));
+        let reminder = tokio::time::timeout(Duration::from_secs(1), reminders.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(reminder.lesson.title, "Math");
+        assert_eq!(reminder.date, NaiveDate::from_ymd_opt(2024, 1, 16).unwrap());
+        assert_eq!(reminder.fire_at, utc("2024-01-15T23:55:00+05:00"));
+        assert_eq!(
+            reminder.starts_at.time(),
+            NaiveTime::from_hms_opt(0, 5, 0).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reminder_follows_schedule_changes() {
+        let mut server = Server::new_async().await;
+        let before = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-01-15".into()))
+            .with_body(schedule_body("2024-01-15", "Math", "12:00:00"))
+            .create_async()
+            .await;
+        let _tomorrow = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-01-16".into()))
+            .with_body("[]")
+            .create_async()
+            .await;
+
+        let clock = ManualClock::new(utc("2024-01-15T09:00:00Z"));
+        let client = Client::new(&server.url());
+        let mut reminders = Box::pin(
+            client
+                .reminders()
+                .subscribe(1, Duration::from_secs(600))
+                .timezone(FixedOffset::east_opt(0).unwrap())
+                .refresh_interval(Duration::from_secs(1800))
+                .with_clock(clock.clone())
+                .stream(),
+        );
+
+        let first = tokio::time::timeout(Duration::from_millis(100), reminders.next()).await;
+        assert!(first.is_err());
+
+        // The lesson moves to 10:00 before the next refresh
+        before.remove_async().await;
+        let _after = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-01-15".into()))
+            .with_body(schedule_body("2024-01-15", "Math", "10:00:00"))
+            .create_async()
+            .await;
+
+        clock.advance(TimeDelta::minutes(30));
+        let pending = tokio::time::timeout(Duration::from_millis(100), reminders.next()).await;
+        assert!(pending.is_err());
+
+        clock.advance(TimeDelta::minutes(20));
+        let reminder = tokio::time::timeout(Duration::from_secs(1), reminders.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(reminder.fire_at, utc("2024-01-15T09:50:00Z"));
+    }
+
+    #[tokio::test]
+    async fn test_reminder_survives_dst_and_renames() {
+        let mut server = Server::new_async().await;
+        let _saturday = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-03-30".into()))
+            .with_body("[]")
+            .create_async()
+            .await;
+        let sunday = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-03-31".into()))
+            .with_body(schedule_body("2024-03-31", "Math", "09:00:00"))
+            .create_async()
+            .await;
+
+        // Started a day before the clocks go forward
+        let clock = ManualClock::new(utc("2024-03-30T12:00:00Z"));
+        let client = Client::new(&server.url());
+        let mut reminders = Box::pin(
+            client
+                .reminders()
+                .subscribe(1, Duration::from_secs(600))
+                .timezone(Dst)
+                .refresh_interval(Duration::from_secs(20 * 3600))
+                .with_clock(clock.clone())
+                .stream(),
+        );
+
+        let early = tokio::time::timeout(Duration::from_millis(100), reminders.next()).await;
+        assert!(early.is_err());
+
+        clock.set(utc("2024-03-31T05:50:00Z"));
+        let reminder = tokio::time::timeout(Duration::from_secs(1), reminders.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(reminder.starts_at, utc("2024-03-31T06:00:00Z"));
+        assert_eq!(reminder.starts_at.offset().local_minus_utc(), 3 * 3600);
+
+        // Renaming the lesson after its reminder fired does not fire it again
+        sunday.remove_async().await;
+        let _renamed = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-03-31".into()))
+            .with_body(schedule_body("2024-03-31", "Algebra", "09:00:00"))
+            .create_async()
+            .await;
+        let _monday = server
+            .mock("GET", "/groups/1/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-04-01".into()))
+            .with_body("[]")
+            .create_async()
+            .await;
+        clock.set(utc("2024-03-31T08:05:00Z"));
+        let again = tokio::time::timeout(Duration::from_millis(200), reminders.next()).await;
+        assert!(again.is_err(), "renamed lesson fired twice");
+    }
+}
@@ -0,0 +1,379 @@
+pub mod table;
+pub mod timetable;
+
+pub use timetable::Timetable;
+
+pub use crate::locale::Language;
+
+use crate::locale::{Label, Locale, NameStyle};
+use crate::{Call, Lesson, Schedule, Weekday};
+use chrono::NaiveDate;
+use std::fmt;
+use std::sync::Arc;
+
+/// Output format of a [`Renderer`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RenderStyle {
+    /// One line per lesson
+    #[default]
+    Compact,
+    /// Aligned table with Unicode box drawing
+    Table,
+    /// GitHub-flavoured Markdown table
+    Markdown,
+    /// HTML subset accepted by Telegram's `parse_mode=HTML`
+    TelegramHtml,
+}
+
+/// Formats lessons, schedules and call timetables as text.
+///
+/// # Examples
+///
+/// ```
+/// use osars::render::{Language, RenderStyle, Renderer};
+///
+/// let renderer = Renderer::new(RenderStyle::Markdown).language(Language::Ru);
+/// let text = renderer.week(&[]);
+/// assert!(text.is_empty());
+/// ```
+#[derive(Clone, Default)]
+pub struct Renderer {
+    style: RenderStyle,
+    locale: Option<Arc<dyn Locale>>,
+}
+
+impl fmt::Debug for Renderer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Renderer")
+            .field("style", &self.style)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Renderer {
+    pub fn new(style: RenderStyle) -> Self {
+        Self {
+            style,
+            locale: None,
+        }
+    }
+
+    /// Sets one of the built-in languages for weekday names and labels
+    pub fn language(self, language: Language) -> Self {
+        self.locale(language)
+    }
+
+    /// Sets a custom locale for weekday names and labels
+    pub fn locale(mut self, locale: impl Locale + 'static) -> Self {
+        self.locale = Some(Arc::new(locale));
+        self
+    }
+
+    fn names(&self) -> &dyn Locale {
+        match &self.locale {
+            Some(locale) => locale.as_ref(),
+            None => &Language::En,
+        }
+    }
+
+    fn headers(&self) -> Vec<String> {
+        [
+            Label::Order,
+            Label::Time,
+            Label::Title,
+            Label::Teacher,
+            Label::Cabinet,
+        ]
+        .into_iter()
+        .map(|label| self.names().label(label).to_string())
+        .collect()
+    }
+
+    /// Renders a single lesson on one line
+    pub fn lesson(&self, lesson: &Lesson) -> String {
+        let time = time_range(lesson);
+        match self.style {
+            RenderStyle::TelegramHtml => format!(
+                "{}. <code>{}</code> {} — <i>{}</i>, {}",
+                lesson.order,
+                time,
+                escape_html(&lesson.title),
+                escape_html(&lesson.teacher),
+                escape_html(&lesson.cabinet)
+            ),
+            _ => format!(
+                "{}. {} {} — {}, {}",
+                lesson.order, time, lesson.title, lesson.teacher, lesson.cabinet
+            ),
+        }
+    }
+
+    /// Renders one day with a localized date heading
+    pub fn schedule(&self, schedule: &Schedule) -> String {
+        let heading = self.day_heading(schedule.date);
+        let mut lessons: Vec<&Lesson> = schedule.lessons.iter().collect();
+        lessons.sort_by_key(|l| (l.order, l.start_time));
+
+        if lessons.is_empty() {
+            return match self.style {
+                RenderStyle::Compact | RenderStyle::Table => {
+                    format!("{}\n{}\n", heading, self.names().label(Label::NoLessons))
+                }
+                RenderStyle::Markdown => {
+                    format!(
+                        "**{}**\n\n{}\n",
+                        heading,
+                        self.names().label(Label::NoLessons)
+                    )
+                }
+                RenderStyle::TelegramHtml => format!(
+                    "<b>{}</b>\n{}\n",
+                    escape_html(&heading),
+                    self.names().label(Label::NoLessons)
+                ),
+            };
+        }
+
+        let headers = self.headers();
+        let rows: Vec<Vec<String>> = lessons
+            .iter()
+            .map(|l| {
+                vec![
+                    l.order.to_string(),
+                    time_range(l),
+                    l.title.clone(),
+                    l.teacher.clone(),
+                    l.cabinet.clone(),
+                ]
+            })
+            .collect();
+
+        match self.style {
+            RenderStyle::Compact => {
+                let mut out = format!("{}\n", heading);
+                for lesson in lessons {
+                    out.push_str(&self.lesson(lesson));
+                    out.push('\n');
+                }
+                out
+            }
+            RenderStyle::Table => format!("{}\n{}", heading, table::boxed(&headers, &rows)),
+            RenderStyle::Markdown => {
+                format!("**{}**\n\n{}", heading, table::markdown(&headers, &rows))
+            }
+            RenderStyle::TelegramHtml => {
+                let mut out = format!("<b>{}</b>\n", escape_html(&heading));
+                for lesson in lessons {
+                    out.push_str(&self.lesson(lesson));
+                    out.push('\n');
+                }
+                out
+            }
+        }
+    }
+
+    /// Renders several days in date order, separated by blank lines
+    pub fn week(&self, schedules: &[Schedule]) -> String {
+        let mut days: Vec<&Schedule> = schedules.iter().collect();
+        days.sort_by_key(|s| s.date);
+        days.iter()
+            .map(|s| self.schedule(s))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Renders a bell timetable grouped by weekday
+    pub fn calls(&self, calls: &[Call]) -> String {
+        let mut calls: Vec<&Call> = calls.iter().collect();
+        calls.sort_by_key(|c| (c.weekday, c.order));
+
+        let mut sections = Vec::new();
+        for weekday in Weekday::ALL {
+            let day: Vec<&&Call> = calls.iter().filter(|c| c.weekday == weekday).collect();
+            if day.is_empty() {
+                continue;
+            }
+
+            let name = self.names().weekday(&weekday, NameStyle::Full);
+            let headers = self.headers()[..2].to_vec();
+            let rows: Vec<Vec<String>> = day
+                .iter()
+                .map(|c| vec![c.order.to_string(), call_range(c)])
+                .collect();
+
+            sections.push(match self.style {
+                RenderStyle::Compact => {
+                    let lines: Vec<String> = day
+                        .iter()
+                        .map(|c| format!("{}. {}", c.order, call_range(c)))
+                        .collect();
+                    format!("{}\n{}\n", name, lines.join("\n"))
+                }
+                RenderStyle::Table => format!("{}\n{}", name, table::boxed(&headers, &rows)),
+                RenderStyle::Markdown => {
+                    format!("**{}**\n\n{}", name, table::markdown(&headers, &rows))
+                }
+                RenderStyle::TelegramHtml => {
+                    let lines: Vec<String> = day
+                        .iter()
+                        .map(|c| format!("{}. <code>{}</code>", c.order, call_range(c)))
+                        .collect();
+                    format!("<b>{}</b>\n{}\n", name, lines.join("\n"))
+                }
+            });
+        }
+
+        sections.join("\n")
+    }
+
+    fn day_heading(&self, date: NaiveDate) -> String {
+        format!(
+            "{}, {}",
+            self.names().weekday(&Weekday::of(date), NameStyle::Full),
+            date.format("%d.%m.%Y")
+        )
+    }
+}
+
+fn time_range(lesson: &Lesson) -> String {
+    format!(
+        "{}–{}",
+        lesson.start_time.format("%H:%M"),
+        lesson.end_time.format("%H:%M")
+    )
+}
+
+fn call_range(call: &Call) -> String {
+    format!(
+        "{}–{}",
+        call.begins.format("%H:%M"),
+        call.ends.format("%H:%M")
+    )
+}
+
+/// Escapes `&`, `<` and `>` for HTML output.
+pub fn escape_html(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn schedule() -> Schedule {
+        Schedule {
+            group_id: 1,
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            lessons: vec![
+                Lesson {
+                    title: "Physics".to_string(),
+                    cabinet: "202".to_string(),
+                    teacher: "Curie".to_string(),
+                    order: 2,
+                    start_time: NaiveTime::from_hms_opt(10, 40, 0).unwrap(),
+                    end_time: NaiveTime::from_hms_opt(12, 10, 0).unwrap(),
+                },
+                Lesson {
+                    title: "Math & <Logic>".to_string(),
+                    cabinet: "101".to_string(),
+                    teacher: "Smith".to_string(),
+                    order: 1,
+                    start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+                    end_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_compact() {
+        let text = Renderer::new(RenderStyle::Compact).schedule(&schedule());
+        assert_eq!(
+            text,
+            "Monday, 15.01.2024\n\
+             1. 09:00–10:30 Math & <Logic> — Smith, 101\n\
+             2. 10:40–12:10 Physics — Curie, 202\n"
+        );
+    }
+
+    #[test]
+    fn test_table_is_aligned() {
+        let text = Renderer::new(RenderStyle::Table)
+            .language(Language::Ru)
+            .schedule(&schedule());
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(lines[0], "Понедельник, 15.01.2024");
+        assert!(lines[1].starts_with('┌'));
+        assert!(lines.last().unwrap().starts_with('└'));
+        let width = lines[1].chars().count();
+        assert!(lines[1..].iter().all(|l| l.chars().count() == width));
+    }
+
+    #[test]
+    fn test_custom_locale() {
+        struct Upper;
+        impl Locale for Upper {
+            fn weekday(&self, _: &crate::Weekday, _: NameStyle) -> &str {
+                "MONDAY"
+            }
+            fn day(&self, _: &crate::Day) -> &str {
+                ""
+            }
+            fn week(&self, _: &crate::Week) -> &str {
+                ""
+            }
+            fn label(&self, _: Label) -> &str {
+                "-"
+            }
+        }
+
+        let text = Renderer::new(RenderStyle::Compact)
+            .locale(Upper)
+            .schedule(&schedule());
+        assert!(text.starts_with("MONDAY, 15.01.2024\n"));
+    }
+
+    #[test]
+    fn test_markdown() {
+        let text = Renderer::new(RenderStyle::Markdown).schedule(&schedule());
+        assert!(text.starts_with("**Monday, 15.01.2024**\n\n| # | Time |"));
+        assert!(text.contains("|---|---|---|---|---|\n"));
+        assert!(text.contains("| 2 | 10:40–12:10 | Physics | Curie | 202 |\n"));
+    }
+
+    #[test]
+    fn test_telegram_html_escapes() {
+        let text = Renderer::new(RenderStyle::TelegramHtml).schedule(&schedule());
+        assert!(text.starts_with("<b>Monday, 15.01.2024</b>\n"));
+        assert!(text.contains("Math &amp; &lt;Logic&gt;"));
+        assert!(text.contains("<code>09:00–10:30</code>"));
+    }
+
+    #[test]
+    fn test_calls_grouped_by_weekday() {
+        let call = |weekday, order, h| Call {
+            call_id: order,
+            weekday,
+            begins: NaiveTime::from_hms_opt(h, 0, 0).unwrap(),
+            ends: NaiveTime::from_hms_opt(h + 1, 30, 0).unwrap(),
+            order,
+        };
+        let text = Renderer::new(RenderStyle::Compact)
+            .language(Language::Ru)
+            .calls(&[
+                call(Weekday::Tuesday, 1, 9),
+                call(Weekday::Monday, 2, 10),
+                call(Weekday::Monday, 1, 8),
+            ]);
+
+        assert_eq!(
+            text,
+            "Понедельник\n1. 08:00–09:30\n2. 10:00–11:30\n\nВторник\n1. 09:00–10:30\n"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+/// Draws a table with Unicode box-drawing borders.
+pub fn boxed(headers: &[String], rows: &[Vec<String>]) -> String {
+    let widths = widths(headers, rows);
+    let rule = |left: &str, mid: &str, right: &str| {
+        let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+        format!("{}{}{}\n", left, segments.join(mid), right)
+    };
+    let line = |cells: &[String]| {
+        let padded: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!(" {} ", pad(cell, *width)))
+            .collect();
+        format!("│{}│\n", padded.join("│"))
+    };
+
+    let mut out = rule("┌", "┬", "┐");
+    out.push_str(&line(headers));
+    out.push_str(&rule("├", "┼", "┤"));
+    for row in rows {
+        out.push_str(&line(row));
+    }
+    out.push_str(&rule("└", "┴", "┘"));
+    out
+}
+
+/// Draws a GitHub-flavoured Markdown table.
+pub fn markdown(headers: &[String], rows: &[Vec<String>]) -> String {
+    let line = |cells: &[String]| {
+        let escaped: Vec<String> = cells.iter().map(|c| c.replace('|', "\\|")).collect();
+        format!("| {} |\n", escaped.join(" | "))
+    };
+
+    let mut out = line(headers);
+    let rule: Vec<String> = headers.iter().map(|_| "---".to_string()).collect();
+    out.push_str(&format!("|{}|\n", rule.join("|")));
+    for row in rows {
+        out.push_str(&line(row));
+    }
+    out
+}
+
+fn widths(headers: &[String], rows: &[Vec<String>]) -> Vec<usize> {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    widths
+}
+
+fn pad(cell: &str, width: usize) -> String {
+    let len = cell.chars().count();
+    format!("{}{}", cell, " ".repeat(width.saturating_sub(len)))
+}
@@ -0,0 +1,422 @@
+use super::escape_html;
+use crate::locale::{Language, Locale, NameStyle};
+use crate::{Call, Lesson, Schedule, Weekday};
+use chrono::NaiveDate;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::sync::Arc;
+
+/// Weekly grid of lessons with one row per lesson period and one column per day.
+///
+/// Periods come from the college's bell schedule combined with the orders
+/// found in the lessons, so periods without any lesson still get a row.
+///
+/// # Examples
+///
+/// ```
+/// use osars::render::Timetable;
+///
+/// let html = Timetable::new(&[], &[]).title("ИС-24-2").to_html();
+/// assert!(html.starts_with("<!DOCTYPE html>"));
+/// ```
+#[derive(Clone)]
+pub struct Timetable<'a> {
+    schedules: &'a [Schedule],
+    calls: &'a [Call],
+    locale: Arc<dyn Locale>,
+    title: Option<String>,
+}
+
+impl fmt::Debug for Timetable<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timetable")
+            .field("schedules", &self.schedules.len())
+            .field("calls", &self.calls.len())
+            .field("title", &self.title)
+            .finish_non_exhaustive()
+    }
+}
+
+struct Grid<'a> {
+    dates: Vec<NaiveDate>,
+    orders: Vec<u32>,
+    cells: HashMap<(NaiveDate, u32), Vec<&'a Lesson>>,
+    subjects: BTreeMap<&'a str, usize>,
+}
+
+impl<'a> Timetable<'a> {
+    pub fn new(schedules: &'a [Schedule], calls: &'a [Call]) -> Self {
+        Self {
+            schedules,
+            calls,
+            locale: Arc::new(Language::default()),
+            title: None,
+        }
+    }
+
+    /// Sets one of the built-in languages for weekday names
+    pub fn language(self, language: Language) -> Self {
+        self.locale(language)
+    }
+
+    /// Sets a custom locale for weekday names
+    pub fn locale(mut self, locale: impl Locale + 'static) -> Self {
+        self.locale = Arc::new(locale);
+        self
+    }
+
+    /// Sets a heading shown above the grid
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    fn grid(&self) -> Grid<'a> {
+        let mut dates = BTreeSet::new();
+        let mut orders: BTreeSet<u32> = self.calls.iter().map(|c| c.order).collect();
+        let mut cells: HashMap<_, Vec<&Lesson>> = HashMap::new();
+        let mut titles = BTreeSet::new();
+
+        for schedule in self.schedules {
+            dates.insert(schedule.date);
+            for lesson in &schedule.lessons {
+                orders.insert(lesson.order);
+                titles.insert(lesson.title.as_str());
+                cells
+                    .entry((schedule.date, lesson.order))
+                    .or_default()
+                    .push(lesson);
+            }
+        }
+
+        Grid {
+            dates: dates.into_iter().collect(),
+            orders: orders.into_iter().collect(),
+            cells,
+            subjects: titles
+                .into_iter()
+                .enumerate()
+                .map(|(i, t)| (t, i))
+                .collect(),
+        }
+    }
+
+    /// Bell times of a period on one weekday
+    fn call_time(&self, weekday: Weekday, order: u32) -> Option<String> {
+        self.calls
+            .iter()
+            .find(|c| c.weekday == weekday && c.order == order)
+            .map(|c| format!("{}–{}", c.begins.format("%H:%M"), c.ends.format("%H:%M")))
+    }
+
+    /// Bell times shared by every shown day that has a call for this order.
+    ///
+    /// `None` when the days disagree; each lesson then shows its own times.
+    fn period_time(&self, order: u32, dates: &[NaiveDate]) -> Option<String> {
+        let times: BTreeSet<String> = if dates.is_empty() {
+            Weekday::ALL
+                .iter()
+                .filter_map(|w| self.call_time(*w, order))
+                .collect()
+        } else {
+            dates
+                .iter()
+                .filter_map(|d| self.call_time(Weekday::of(*d), order))
+                .collect()
+        };
+        if times.len() == 1 {
+            times.into_iter().next()
+        } else {
+            None
+        }
+    }
+
+    fn day_label(&self, date: NaiveDate) -> String {
+        format!(
+            "{} {}",
+            self.locale.weekday(&Weekday::of(date), NameStyle::Full),
+            date.format("%d.%m")
+        )
+    }
+
+    /// Renders a self-contained HTML page with inline styles.
+    pub fn to_html(&self) -> String {
+        let grid = self.grid();
+        let mut out = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+
+        if let Some(title) = &self.title {
+            out.push_str(&format!("<title>{}</title>\n", escape_html(title)));
+        }
+
+        out.push_str("<style>\n");
+        out.push_str(
+            "table.timetable { border-collapse: collapse; font-family: sans-serif; }\n\
+             .timetable th, .timetable td { border: 1px solid #999; padding: 4px 8px; vertical-align: top; }\n\
+             .timetable td.empty { background: #f4f4f4; }\n\
+             .timetable .time, .timetable .details { display: block; font-size: 0.8em; color: #555; }\n",
+        );
+        for index in grid.subjects.values() {
+            out.push_str(&format!(
+                ".timetable td.subject-{} {{ background: hsl({}, 70%, 90%); }}\n",
+                index,
+                (index * 47) % 360
+            ));
+        }
+        out.push_str("</style>\n</head>\n<body>\n");
+
+        if let Some(title) = &self.title {
+            out.push_str(&format!("<h1>{}</h1>\n", escape_html(title)));
+        }
+
+        out.push_str("<table class=\"timetable\">\n<thead>\n<tr><th>#</th>");
+        for date in &grid.dates {
+            out.push_str(&format!("<th>{}</th>", escape_html(&self.day_label(*date))));
+        }
+        out.push_str("</tr>\n</thead>\n<tbody>\n");
+
+        // Remaining rows covered by a merged empty cell, per column
+        let mut covered = vec![0usize; grid.dates.len()];
+
+        for (row, order) in grid.orders.iter().enumerate() {
+            out.push_str(&format!("<tr><th>{}", order));
+            let shared_time = self.period_time(*order, &grid.dates);
+            if let Some(time) = &shared_time {
+                out.push_str(&format!("<span class=\"time\">{}</span>", time));
+            }
+            out.push_str("</th>");
+
+            for (column, date) in grid.dates.iter().enumerate() {
+                if covered[column] > 0 {
+                    covered[column] -= 1;
+                    continue;
+                }
+
+                match grid.cells.get(&(*date, *order)) {
+                    Some(lessons) => {
+                        let classes: Vec<String> = lessons
+                            .iter()
+                            .map(|l| format!("subject-{}", grid.subjects[l.title.as_str()]))
+                            .collect::<BTreeSet<_>>()
+                            .into_iter()
+                            .collect();
+                        out.push_str(&format!("<td class=\"lesson {}\">", classes.join(" ")));
+                        if shared_time.is_none()
+                            && let Some(time) = self.call_time(Weekday::of(*date), *order)
+                        {
+                            out.push_str(&format!("<span class=\"time\">{}</span>", time));
+                        }
+                        for lesson in lessons {
+                            out.push_str(&format!(
+                                "<div><strong>{}</strong><span class=\"details\">{}, {}</span></div>",
+                                escape_html(&lesson.title),
+                                escape_html(&lesson.teacher),
+                                escape_html(&lesson.cabinet)
+                            ));
+                        }
+                        out.push_str("</td>");
+                    }
+                    None => {
+                        let span = grid.orders[row..]
+                            .iter()
+                            .take_while(|o| !grid.cells.contains_key(&(*date, **o)))
+                            .count();
+                        covered[column] = span - 1;
+                        if span > 1 {
+                            out.push_str(&format!(
+                                "<td class=\"empty\" rowspan=\"{}\"></td>",
+                                span
+                            ));
+                        } else {
+                            out.push_str("<td class=\"empty\"></td>");
+                        }
+                    }
+                }
+            }
+            out.push_str("</tr>\n");
+        }
+
+        out.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+        out
+    }
+
+    /// Renders the grid as a Markdown table.
+    pub fn to_markdown(&self) -> String {
+        let grid = self.grid();
+        let mut out = String::new();
+
+        if let Some(title) = &self.title {
+            out.push_str(&format!("## {}\n\n", escape_markdown(title)));
+        }
+
+        let mut headers = vec!["#".to_string()];
+        headers.extend(grid.dates.iter().map(|d| self.day_label(*d)));
+        out.push_str(&format!("| {} |\n", headers.join(" | ")));
+        out.push_str(&format!("|{}|\n", vec!["---"; headers.len()].join("|")));
+
+        for order in &grid.orders {
+            let shared_time = self.period_time(*order, &grid.dates);
+            let mut row = vec![match &shared_time {
+                Some(time) => format!("{}<br>{}", order, time),
+                None => order.to_string(),
+            }];
+            for date in &grid.dates {
+                let cell = grid
+                    .cells
+                    .get(&(*date, *order))
+                    .map(|lessons| {
+                        let lessons = lessons
+                            .iter()
+                            .map(|l| {
+                                format!(
+                                    "**{}**<br>{}, {}",
+                                    escape_markdown(&l.title),
+                                    escape_markdown(&l.teacher),
+                                    escape_markdown(&l.cabinet)
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                            .join("<br>");
+                        match shared_time
+                            .is_none()
+                            .then(|| self.call_time(Weekday::of(*date), *order))
+                            .flatten()
+                        {
+                            Some(time) => format!("{}<br>{}", time, lessons),
+                            None => lessons,
+                        }
+                    })
+                    .unwrap_or_default();
+                row.push(cell);
+            }
+            out.push_str(&format!("| {} |\n", row.join(" | ")));
+        }
+
+        out
+    }
+}
+
+fn escape_markdown(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '\n' | '\r' => out.push(' '),
+            '|' | '*' | '_' | '`' | '[' | ']' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveTime;
+
+    fn lesson(order: u32, title: &str) -> Lesson {
+        Lesson {
+            title: title.to_string(),
+            cabinet: "101".to_string(),
+            teacher: "Smith".to_string(),
+            order,
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+        }
+    }
+
+    fn call(weekday: Weekday, order: u32, hour: u32) -> Call {
+        Call {
+            call_id: order,
+            weekday,
+            begins: NaiveTime::from_hms_opt(hour, 0, 0).unwrap(),
+            ends: NaiveTime::from_hms_opt(hour + 1, 30, 0).unwrap(),
+            order,
+        }
+    }
+
+    fn week() -> Vec<Schedule> {
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        vec![
+            Schedule {
+                group_id: 1,
+                date: monday,
+                lessons: vec![lesson(1, "Math"), lesson(4, "<script>")],
+            },
+            Schedule {
+                group_id: 1,
+                date: monday.succ_opt().unwrap(),
+                lessons: vec![lesson(2, "Math")],
+            },
+        ]
+    }
+
+    #[test]
+    fn test_html_merges_empty_cells_and_escapes() {
+        let calls = [
+            call(Weekday::Monday, 1, 8),
+            call(Weekday::Monday, 2, 10),
+            call(Weekday::Monday, 3, 12),
+            call(Weekday::Tuesday, 1, 8),
+        ];
+        let schedules = week();
+        let html = Timetable::new(&schedules, &calls).title("A & B").to_html();
+
+        assert!(html.contains("<title>A &amp; B</title>"));
+        assert!(html.contains("&lt;script&gt;"));
+        assert!(!html.contains("<script>"));
+        // Monday: orders 2 and 3 are empty and merged
+        assert!(html.contains("<td class=\"empty\" rowspan=\"2\"></td>"));
+        // Tuesday: order 1 empty alone, orders 3 and 4 merged
+        assert!(html.contains("<td class=\"empty\"></td>"));
+        assert_eq!(html.matches("rowspan=\"2\"").count(), 2);
+        assert!(html.contains("<span class=\"time\">08:00–09:30</span>"));
+        assert_eq!(html.matches("class=\"lesson subject-1\"").count(), 2);
+    }
+
+    #[test]
+    fn test_period_times_follow_weekday_bells() {
+        let calls = [call(Weekday::Monday, 1, 8), call(Weekday::Tuesday, 1, 9)];
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let schedules: Vec<Schedule> = [monday, monday.succ_opt().unwrap()]
+            .into_iter()
+            .map(|date| Schedule {
+                group_id: 1,
+                date,
+                lessons: vec![lesson(1, "Math")],
+            })
+            .collect();
+
+        let html = Timetable::new(&schedules, &calls).to_html();
+        assert!(html.contains("<tr><th>1</th>"));
+        assert!(
+            html.contains("<td class=\"lesson subject-0\"><span class=\"time\">08:00–09:30</span>")
+        );
+        assert!(
+            html.contains("<td class=\"lesson subject-0\"><span class=\"time\">09:00–10:30</span>")
+        );
+
+        let markdown = Timetable::new(&schedules, &calls).to_markdown();
+        assert!(markdown.contains("| 1 | 08:00–09:30<br>**Math**"));
+        assert!(markdown.contains("| 09:00–10:30<br>**Math**"));
+    }
+
+    #[test]
+    fn test_markdown_grid() {
+        let calls = [call(Weekday::Monday, 1, 8)];
+        let schedules = week();
+        let markdown = Timetable::new(&schedules, &calls)
+            .language(Language::Ru)
+            .to_markdown();
+        let lines: Vec<&str> = markdown.lines().collect();
+
+        assert_eq!(lines[0], "| # | Понедельник 15.01 | Вторник 16.01 |");
+        assert_eq!(lines[1], "|---|---|---|");
+        assert_eq!(lines[2], "| 1<br>08:00–09:30 | **Math**<br>Smith, 101 |  |");
+        assert_eq!(lines[4], "| 4 | **&lt;script&gt;**<br>Smith, 101 |  |");
+    }
+}
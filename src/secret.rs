@@ -0,0 +1,247 @@
+use crate::error::{Error, Result};
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use zeroize::Zeroizing;
+
+#[cfg(feature = "logging")]
+use tracing::debug;
+
+/// A bearer token that never shows up in logs.
+///
+/// `Debug` and `Display` print `[REDACTED]`. Every clone owns its own copy
+/// of the token, and each copy's memory is wiped when that clone is dropped.
+///
+/// # Examples
+///
+/// ```
+/// use osars::SecretToken;
+///
+/// let token = SecretToken::new("s3cr3t");
+/// assert_eq!(format!("{:?}", token), "SecretToken([REDACTED])");
+/// assert_eq!(token.expose(), "s3cr3t");
+/// ```
+#[derive(Clone)]
+pub struct SecretToken(Zeroizing<String>);
+
+impl SecretToken {
+    pub fn new(token: impl Into<String>) -> Self {
+        Self(Zeroizing::new(token.into()))
+    }
+
+    /// Reads a token from an environment variable.
+    ///
+    /// Fails with [`Error::NotFound`] if the variable is unset and with
+    /// [`Error::Validation`] if it is empty.
+    pub fn from_env(var: &str) -> Result<Self> {
+        let value = Zeroizing::new(
+            std::env::var(var)
+                .map_err(|_| Error::NotFound(format!("environment variable {}", var)))?,
+        );
+        Self::parse(&value, || format!("environment variable {}", var))
+    }
+
+    /// Reads a token from the first line of a file.
+    ///
+    /// On Unix the file must not be accessible by group or others
+    /// (`chmod 600`), otherwise this fails with [`Error::Validation`].
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        check_permissions(path)?;
+
+        #[cfg(feature = "logging")]
+        debug!("Reading token from {}", path.display());
+
+        let raw = Zeroizing::new(std::fs::read_to_string(path)?);
+        Self::parse(&raw, || format!("token file {}", path.display()))
+    }
+
+    /// The token itself; avoid keeping copies of it around
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    fn parse(raw: &str, source: impl FnOnce() -> String) -> Result<Self> {
+        let token = raw.lines().next().unwrap_or_default().trim();
+        if token.is_empty() {
+            return Err(Error::Validation(format!("{} is empty", source())));
+        }
+        Ok(Self::new(token))
+    }
+}
+
+impl fmt::Debug for SecretToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretToken([REDACTED])")
+    }
+}
+
+impl fmt::Display for SecretToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+impl From<String> for SecretToken {
+    fn from(token: String) -> Self {
+        Self::new(token)
+    }
+}
+
+impl From<&str> for SecretToken {
+    fn from(token: &str) -> Self {
+        Self::new(token)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretToken {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::new)
+    }
+}
+
+#[cfg(unix)]
+fn check_permissions(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mode = std::fs::metadata(path)?.permissions().mode();
+    if mode & 0o077 != 0 {
+        return Err(Error::Validation(format!(
+            "token file {} is accessible by other users (mode {:o}), run chmod 600",
+            path.display(),
+            mode & 0o777
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn check_permissions(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// A source of tokens, such as a secret manager or keyring.
+///
+/// Closures returning `Result<SecretToken>` are providers too.
+pub trait CredentialProvider {
+    fn token(&self) -> Result<SecretToken>;
+}
+
+impl<F> CredentialProvider for F
+where
+    F: Fn() -> Result<SecretToken>,
+{
+    fn token(&self) -> Result<SecretToken> {
+        self()
+    }
+}
+
+/// Reads the token from an environment variable, see [`SecretToken::from_env`].
+#[derive(Debug, Clone)]
+pub struct EnvCredentials {
+    pub var: String,
+}
+
+impl EnvCredentials {
+    pub fn new(var: &str) -> Self {
+        Self {
+            var: var.to_string(),
+        }
+    }
+}
+
+impl CredentialProvider for EnvCredentials {
+    fn token(&self) -> Result<SecretToken> {
+        SecretToken::from_env(&self.var)
+    }
+}
+
+/// Reads the token from a file, see [`SecretToken::from_file`].
+#[derive(Debug, Clone)]
+pub struct FileCredentials {
+    pub path: PathBuf,
+}
+
+impl FileCredentials {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl CredentialProvider for FileCredentials {
+    fn token(&self) -> Result<SecretToken> {
+        SecretToken::from_file(&self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Auth, Client};
+
+    #[test]
+    fn test_token_is_redacted() {
+        let client = Client::new("https://api.example.com")
+            .authenticated()
+            .with_token("s3cr3t");
+
+        assert!(!format!("{:?}", client).contains("s3cr3t"));
+        assert_eq!(SecretToken::new("s3cr3t").to_string(), "[REDACTED]");
+    }
+
+    #[test]
+    fn test_env_credentials() {
+        // Set by cargo for every test run; setting a variable here would race
+        // with other tests reading the environment
+        let token = EnvCredentials::new("CARGO_PKG_NAME").token().unwrap();
+        assert_eq!(token.expose(), env!("CARGO_PKG_NAME"));
+        assert!(matches!(
+            SecretToken::from_env("OSARS_TEST_UNSET_TOKEN"),
+            Err(Error::NotFound(_))
+        ));
+
+        let parsed = SecretToken::parse(" from-env \nrest", String::new).unwrap();
+        assert_eq!(parsed.expose(), "from-env");
+        assert!(matches!(
+            SecretToken::parse(" \n", || "test".to_string()),
+            Err(Error::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_closure_credentials() {
+        let provider = || Ok(SecretToken::new("from-closure"));
+        let client = Client::new("https://api.example.com")
+            .authenticated()
+            .with_credentials(&provider)
+            .unwrap();
+        assert!(client.auth.has_token());
+
+        let failing = || Err(Error::NotFound("keyring entry".to_string()));
+        assert!(matches!(
+            Auth::from_provider(&failing),
+            Err(Error::NotFound(_))
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_file_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = std::env::temp_dir().join(format!("osars-token-{}", std::process::id()));
+        std::fs::write(&path, "from-file\n").unwrap();
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(matches!(
+            SecretToken::from_file(&path),
+            Err(Error::Validation(_))
+        ));
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        let token = FileCredentials::new(&path).token().unwrap();
+        assert_eq!(token.expose(), "from-file");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,541 @@
+use crate::api::sync::{SyncOutcome, SyncPlan};
+use crate::api::upload::UploadSummary;
+use crate::error::{Error, Result};
+use crate::models::validation::DATE_FORMAT;
+use crate::{
+    CallRequest, Campus, LessonRequest, ParserApi, Problem, UpdateCallsRequest,
+    UpdateGroupsRequest, UpdateLessonsRequest, Validate,
+};
+use chrono::NaiveDate;
+use futures::future::BoxFuture;
+use std::collections::{BTreeSet, HashMap};
+
+#[cfg(feature = "logging")]
+use tracing::{debug, warn};
+
+/// A lesson as extracted by a [`ScheduleSource`], with the group given by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLesson {
+    pub group: String,
+    pub date: NaiveDate,
+    pub order: u32,
+    pub title: String,
+    pub teacher: String,
+    pub cabinet: String,
+}
+
+/// College-specific extraction of schedule data.
+///
+/// Implement this for a scraper and let [`ParserRunner`] take care of
+/// resolving ids, validation, diffing and uploading.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use futures::future::BoxFuture;
+/// use osars::source::{ScheduleSource, SourceLesson};
+/// use osars::{Campus, Result};
+///
+/// struct Static;
+///
+/// impl ScheduleSource for Static {
+///     fn campuses(&self) -> BoxFuture<'_, Result<Vec<String>>> {
+///         Box::pin(async { Ok(vec!["Main".to_string()]) })
+///     }
+///
+///     fn groups<'a>(&'a self, _campus: &'a Campus) -> BoxFuture<'a, Result<Vec<String>>> {
+///         Box::pin(async { Ok(vec!["ИС-24-2".to_string()]) })
+///     }
+///
+///     fn lessons<'a>(
+///         &'a self,
+///         _campus: &'a Campus,
+///         _from: NaiveDate,
+///         _to: NaiveDate,
+///     ) -> BoxFuture<'a, Result<Vec<SourceLesson>>> {
+///         Box::pin(async { Ok(vec![]) })
+///     }
+/// }
+/// ```
+pub trait ScheduleSource: Send + Sync {
+    /// Names of the campuses this source covers
+    fn campuses(&self) -> BoxFuture<'_, Result<Vec<String>>>;
+
+    /// Names of all groups of a campus
+    fn groups<'a>(&'a self, campus: &'a Campus) -> BoxFuture<'a, Result<Vec<String>>>;
+
+    /// Bell schedule of the college; empty leaves the server's calls untouched
+    fn calls(&self) -> BoxFuture<'_, Result<Vec<CallRequest>>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+
+    /// Lessons of a campus between `from` and `to`, inclusive
+    fn lessons<'a>(
+        &'a self,
+        campus: &'a Campus,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> BoxFuture<'a, Result<Vec<SourceLesson>>>;
+}
+
+/// Summary of one campus in a [`RunReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CampusRun {
+    pub campus_id: u32,
+    pub name: String,
+    pub groups: usize,
+    pub lessons: usize,
+    /// Lessons the source returned outside the requested dates, not uploaded
+    pub out_of_range: usize,
+}
+
+/// What a [`ParserRunner`] did.
+#[derive(Debug, Default)]
+pub struct RunReport {
+    pub campuses: Vec<CampusRun>,
+    /// Campus names from the source that the API does not know
+    pub unknown_campuses: Vec<String>,
+    /// Group names used by lessons but missing from their campus
+    pub unknown_groups: Vec<String>,
+    /// Number of calls uploaded
+    pub calls: usize,
+    /// Validation problems; affected payloads were not uploaded
+    pub problems: Vec<Problem>,
+    /// Lesson diff of all campuses whose lessons passed validation, absent if none did
+    pub plan: Option<SyncPlan>,
+    /// Upload of [`plan`](Self::plan), with chunk ranges into its `upload`
+    pub upload: Option<UploadSummary>,
+}
+
+impl RunReport {
+    /// Whether everything the source produced reached the server
+    pub fn is_success(&self) -> bool {
+        self.unknown_campuses.is_empty()
+            && self.unknown_groups.is_empty()
+            && self.problems.is_empty()
+            && self.upload.as_ref().is_none_or(UploadSummary::is_success)
+    }
+
+    /// Appends a campus sync, shifting its chunks behind earlier ones
+    fn merge(&mut self, outcome: SyncOutcome) {
+        let plan = self.plan.get_or_insert_with(SyncPlan::default);
+        let upload = self.upload.get_or_insert_with(UploadSummary::default);
+        let offset = plan.upload.len();
+        let first = upload.chunks.len();
+
+        plan.added.extend(outcome.plan.added);
+        plan.changed.extend(outcome.plan.changed);
+        plan.removed.extend(outcome.plan.removed);
+        plan.unchanged += outcome.plan.unchanged;
+        plan.upload.extend(outcome.plan.upload);
+        upload
+            .chunks
+            .extend(outcome.upload.chunks.into_iter().map(|mut chunk| {
+                chunk.index += first;
+                chunk.range = chunk.range.start + offset..chunk.range.end + offset;
+                chunk
+            }));
+    }
+}
+
+/// Drives a [`ScheduleSource`] against the parser API.
+///
+/// Campuses are looked up in the client's default college (see
+/// [`Client::with_college`](crate::Client::with_college)). For every campus
+/// the runner uploads groups, then uploads calls, then syncs lessons of the
+/// date range so that only changed days are sent. Lessons are validated and
+/// synced per campus, so problems in one campus do not hold back the others.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example(source: impl osars::source::ScheduleSource) -> osars::Result<()> {
+/// use chrono::NaiveDate;
+/// use osars::Client;
+/// use osars::source::ParserRunner;
+///
+/// let client = Client::new("https://api.thisishyum.ru/schedule_api/tyumen").with_college(1);
+/// let parser = client.authenticated().with_token("token").parser();
+///
+/// let report = ParserRunner::new(parser, source)
+///     .dates(
+///         NaiveDate::from_ymd_opt(2024, 9, 1).unwrap(),
+///         NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+///     )
+///     .run()
+///     .await?;
+/// assert!(report.is_success());
+/// # Ok(())
+/// # }
+/// ```
+pub struct ParserRunner<S> {
+    parser: ParserApi,
+    source: S,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+}
+
+impl<S: ScheduleSource> ParserRunner<S> {
+    pub fn new(parser: ParserApi, source: S) -> Self {
+        Self {
+            parser,
+            source,
+            from: None,
+            to: None,
+        }
+    }
+
+    /// Sets the inclusive range of lesson dates to extract
+    pub fn dates(mut self, from: NaiveDate, to: NaiveDate) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+
+    /// Runs for the range set with [`dates`](Self::dates)
+    pub async fn run(&self) -> Result<RunReport> {
+        let (Some(from), Some(to)) = (self.from, self.to) else {
+            return Err(Error::Validation(
+                "date range is required, use dates()".to_string(),
+            ));
+        };
+        self.run_for(from, to).await
+    }
+
+    /// Runs for an explicit inclusive date range
+    pub async fn run_for(&self, from: NaiveDate, to: NaiveDate) -> Result<RunReport> {
+        if from > to {
+            return Err(Error::Validation(format!(
+                "date range starts after it ends: {} > {}",
+                from, to
+            )));
+        }
+
+        let client = &self.parser.client.client;
+        let mut report = RunReport::default();
+
+        let known = client.campuses()?.send().await?;
+        let mut campuses = Vec::new();
+        for name in self.source.campuses().await? {
+            match known.iter().find(|c| same_name(&c.name, &name)) {
+                Some(campus) => campuses.push(campus.clone()),
+                None => {
+                    #[cfg(feature = "logging")]
+                    warn!("Campus '{}' is not known to the API", name);
+                    report.unknown_campuses.push(name);
+                }
+            }
+        }
+
+        for campus in &campuses {
+            let request = UpdateGroupsRequest {
+                campus_id: campus.id,
+                student_group_names: self.source.groups(campus).await?,
+            };
+            let problems = request.problems();
+            if problems.is_empty() {
+                self.parser.update_groups(request.clone()).await?;
+            } else {
+                report.problems.extend(scoped(&campus.name, problems));
+            }
+            report.campuses.push(CampusRun {
+                campus_id: campus.id,
+                name: campus.name.clone(),
+                groups: request.student_group_names.len(),
+                lessons: 0,
+                out_of_range: 0,
+            });
+        }
+
+        let calls = UpdateCallsRequest {
+            calls: self.source.calls().await?,
+        };
+        let problems = calls.problems();
+        if !problems.is_empty() {
+            report.problems.extend(problems);
+        } else if !calls.calls.is_empty() {
+            report.calls = calls.calls.len();
+            self.parser.update_calls(calls).await?;
+        }
+
+        let mut unknown_groups = BTreeSet::new();
+        for (i, campus) in campuses.iter().enumerate() {
+            let extracted = self.source.lessons(campus, from, to).await?;
+            let groups: HashMap<String, u32> = client
+                .groups(campus.id)
+                .send()
+                .await?
+                .into_iter()
+                .map(|g| (normalize(&g.name), g.id))
+                .collect();
+
+            let run = &mut report.campuses[i];
+            let mut lessons = Vec::new();
+            for lesson in extracted {
+                if lesson.date < from || lesson.date > to {
+                    run.out_of_range += 1;
+                    continue;
+                }
+                let Some(group_id) = groups.get(&normalize(&lesson.group)) else {
+                    unknown_groups.insert(lesson.group);
+                    continue;
+                };
+                run.lessons += 1;
+                lessons.push(LessonRequest {
+                    group_id: *group_id,
+                    order: lesson.order,
+                    title: lesson.title,
+                    teacher: lesson.teacher,
+                    cabinet: lesson.cabinet,
+                    date: lesson.date.format(DATE_FORMAT).to_string(),
+                });
+            }
+
+            let request = UpdateLessonsRequest { lessons };
+            let problems = request.problems();
+            if problems.is_empty() {
+                let outcome = self.parser.sync_lessons(request.lessons).send().await?;
+                report.merge(outcome);
+            } else {
+                report.problems.extend(scoped(&campus.name, problems));
+            }
+        }
+        report.unknown_groups = unknown_groups.into_iter().collect();
+
+        #[cfg(feature = "logging")]
+        debug!(
+            "Parser run finished: {} campuses, {} problems",
+            report.campuses.len(),
+            report.problems.len()
+        );
+
+        Ok(report)
+    }
+}
+
+fn normalize(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    normalize(a) == normalize(b)
+}
+
+fn scoped(campus: &str, problems: Vec<Problem>) -> impl Iterator<Item = Problem> + '_ {
+    problems.into_iter().map(move |p| Problem {
+        field: format!("{}: {}", campus, p.field),
+        message: p.message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Client;
+    use mockito::{Matcher, Server};
+
+    struct Fake;
+
+    impl ScheduleSource for Fake {
+        fn campuses(&self) -> BoxFuture<'_, Result<Vec<String>>> {
+            Box::pin(async { Ok(vec!["main ".to_string(), "Annex".to_string()]) })
+        }
+
+        fn groups<'a>(&'a self, _campus: &'a Campus) -> BoxFuture<'a, Result<Vec<String>>> {
+            Box::pin(async { Ok(vec!["A".to_string(), "B".to_string()]) })
+        }
+
+        fn lessons<'a>(
+            &'a self,
+            _campus: &'a Campus,
+            from: NaiveDate,
+            _to: NaiveDate,
+        ) -> BoxFuture<'a, Result<Vec<SourceLesson>>> {
+            let lesson = |group: &str, date: NaiveDate| SourceLesson {
+                group: group.to_string(),
+                date,
+                order: 1,
+                title: "Math".to_string(),
+                teacher: "Smith".to_string(),
+                cabinet: "101".to_string(),
+            };
+            Box::pin(async move {
+                Ok(vec![
+                    lesson("a", from),
+                    lesson("Z", from),
+                    lesson("A", from.pred_opt().unwrap()),
+                ])
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runner_resolves_uploads_and_reports() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/colleges/1/campuses")
+            .with_body(r#"[{"campusId":10,"name":"Main","collegeId":1}]"#)
+            .create_async()
+            .await;
+        let groups = server
+            .mock("POST", "/parser/groups")
+            .match_body(Matcher::PartialJsonString(
+                r#"{"campusId":10,"studentGroupNames":["A","B"]}"#.to_string(),
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+        let calls = server
+            .mock("POST", "/parser/calls")
+            .expect(0)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/campuses/10/groups")
+            .with_body(r#"[{"studentGroupId":5,"name":"A","campusId":10}]"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/groups/5/schedules")
+            .match_query(Matcher::UrlEncoded("date".into(), "2024-01-15".into()))
+            .with_body("[]")
+            .create_async()
+            .await;
+        let lessons = server
+            .mock("POST", "/parser/lessons/replace")
+            .match_body(Matcher::PartialJsonString(
+                r#"{"groupId":5,"from":"2024-01-15","to":"2024-01-15","lessons":[{"groupId":5,"date":"2024-01-15","order":1}]}"#.to_string(),
+            ))
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url()).with_college(1);
+        let parser = client.authenticated().with_token("token").parser();
+        let day = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        let report = ParserRunner::new(parser, Fake)
+            .dates(day, day)
+            .run()
+            .await
+            .unwrap();
+
+        groups.assert_async().await;
+        calls.assert_async().await;
+        lessons.assert_async().await;
+
+        assert_eq!(
+            report.campuses,
+            vec![CampusRun {
+                campus_id: 10,
+                name: "Main".to_string(),
+                groups: 2,
+                lessons: 1,
+                out_of_range: 1,
+            }]
+        );
+        assert_eq!(report.unknown_campuses, vec!["Annex"]);
+        assert_eq!(report.unknown_groups, vec!["Z"]);
+        assert_eq!(report.plan.as_ref().unwrap().added.len(), 1);
+        assert!(!report.is_success());
+    }
+
+    struct OneBadCampus;
+
+    impl ScheduleSource for OneBadCampus {
+        fn campuses(&self) -> BoxFuture<'_, Result<Vec<String>>> {
+            Box::pin(async { Ok(vec!["Main".to_string(), "Annex".to_string()]) })
+        }
+
+        fn groups<'a>(&'a self, campus: &'a Campus) -> BoxFuture<'a, Result<Vec<String>>> {
+            Box::pin(async { Ok(vec![campus.name.clone()]) })
+        }
+
+        fn lessons<'a>(
+            &'a self,
+            campus: &'a Campus,
+            from: NaiveDate,
+            _to: NaiveDate,
+        ) -> BoxFuture<'a, Result<Vec<SourceLesson>>> {
+            let lesson = SourceLesson {
+                group: campus.name.clone(),
+                date: from,
+                order: 1,
+                title: "Math".to_string(),
+                teacher: "Smith".to_string(),
+                cabinet: "101".to_string(),
+            };
+            let copies = if campus.name == "Annex" { 2 } else { 1 };
+            Box::pin(async move { Ok(vec![lesson; copies]) })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_runner_syncs_valid_campuses_only() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/colleges/1/campuses")
+            .with_body(
+                r#"[{"campusId":10,"name":"Main","collegeId":1},{"campusId":11,"name":"Annex","collegeId":1}]"#,
+            )
+            .create_async()
+            .await;
+        server.mock("POST", "/parser/groups").create_async().await;
+        for (campus, group, name) in [(10, 5, "Main"), (11, 6, "Annex")] {
+            server
+                .mock("GET", format!("/campuses/{}/groups", campus).as_str())
+                .with_body(format!(
+                    r#"[{{"studentGroupId":{},"name":"{}","campusId":{}}}]"#,
+                    group, name, campus
+                ))
+                .create_async()
+                .await;
+        }
+        server
+            .mock("GET", "/groups/5/schedules")
+            .match_query(Matcher::Any)
+            .with_body("[]")
+            .create_async()
+            .await;
+        let main = server
+            .mock("POST", "/parser/lessons/replace")
+            .match_body(Matcher::PartialJsonString(r#"{"groupId":5}"#.to_string()))
+            .expect(1)
+            .create_async()
+            .await;
+        let annex = server
+            .mock("POST", "/parser/lessons/replace")
+            .match_body(Matcher::PartialJsonString(r#"{"groupId":6}"#.to_string()))
+            .expect(0)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url()).with_college(1);
+        let parser = client.authenticated().with_token("token").parser();
+        let day = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        let report = ParserRunner::new(parser, OneBadCampus)
+            .run_for(day, day)
+            .await
+            .unwrap();
+
+        main.assert_async().await;
+        annex.assert_async().await;
+        assert_eq!(report.problems.len(), 1);
+        assert!(report.problems[0].field.starts_with("Annex: "));
+        assert_eq!(report.plan.as_ref().unwrap().added.len(), 1);
+        assert_eq!(report.upload.as_ref().unwrap().uploaded_lessons(), 1);
+        assert!(!report.is_success());
+    }
+
+    #[tokio::test]
+    async fn test_runner_requires_dates() {
+        let client = Client::new("http://localhost").with_college(1);
+        let parser = client.authenticated().parser();
+
+        let result = ParserRunner::new(parser, Fake).run().await;
+
+        assert!(matches!(result, Err(Error::Validation(_))));
+    }
+}
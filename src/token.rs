@@ -0,0 +1,293 @@
+use crate::error::Result;
+use crate::secret::{EnvCredentials, SecretToken};
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+#[cfg(feature = "logging")]
+use tracing::debug;
+
+/// Supplies the bearer token for every private request.
+///
+/// [`Auth`](crate::Auth) asks for a token before each request, so a rotated
+/// token is picked up without rebuilding the client. When the server answers
+/// `401 Unauthorized` and [`can_refresh`](Self::can_refresh) is true,
+/// [`refresh`](Self::refresh) is called once and the request is retried with
+/// the new token.
+pub trait TokenProvider: Send + Sync {
+    fn token(&self) -> BoxFuture<'_, Result<SecretToken>>;
+
+    /// Called after the server rejected the current token; defaults to [`token`](Self::token)
+    fn refresh(&self) -> BoxFuture<'_, Result<SecretToken>> {
+        self.token()
+    }
+
+    /// Whether [`refresh`](Self::refresh) may return a different token than the
+    /// one just rejected; `false` by default, so a `401` is not retried
+    fn can_refresh(&self) -> bool {
+        false
+    }
+}
+
+/// A fixed token.
+impl TokenProvider for SecretToken {
+    fn token(&self) -> BoxFuture<'_, Result<SecretToken>> {
+        futures::future::ready(Ok(self.clone())).boxed()
+    }
+}
+
+/// Reads the environment variable on every request.
+impl TokenProvider for EnvCredentials {
+    fn token(&self) -> BoxFuture<'_, Result<SecretToken>> {
+        futures::future::ready(SecretToken::from_env(&self.var)).boxed()
+    }
+}
+
+/// Reads a token file and re-reads it whenever its modification time changes.
+///
+/// The same permission checks as [`SecretToken::from_file`] apply. The file
+/// is read on tokio's blocking thread pool.
+#[derive(Debug)]
+pub struct WatchedTokenFile {
+    path: PathBuf,
+    cached: Mutex<Option<(SystemTime, SecretToken)>>,
+}
+
+impl WatchedTokenFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            cached: Mutex::new(None),
+        }
+    }
+
+    async fn load(&self, force: bool) -> Result<SecretToken> {
+        let modified = tokio::fs::metadata(&self.path).await?.modified()?;
+
+        if !force
+            && let Some((at, token)) = self
+                .cached
+                .lock()
+                .expect("token cache lock poisoned")
+                .as_ref()
+            && *at == modified
+        {
+            return Ok(token.clone());
+        }
+
+        #[cfg(feature = "logging")]
+        debug!("Reloading token from {}", self.path.display());
+
+        let path = self.path.clone();
+        let token = tokio::task::spawn_blocking(move || SecretToken::from_file(path))
+            .await
+            .map_err(std::io::Error::other)??;
+        *self.cached.lock().expect("token cache lock poisoned") = Some((modified, token.clone()));
+        Ok(token)
+    }
+}
+
+impl TokenProvider for WatchedTokenFile {
+    fn token(&self) -> BoxFuture<'_, Result<SecretToken>> {
+        self.load(false).boxed()
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<SecretToken>> {
+        self.load(true).boxed()
+    }
+
+    fn can_refresh(&self) -> bool {
+        true
+    }
+}
+
+/// Fetches the token with an async callback, e.g. from a secret manager.
+///
+/// # Examples
+///
+/// ```
+/// use osars::{Client, SecretToken, TokenCallback};
+///
+/// let parser = Client::new("https://api.thisishyum.ru/schedule_api/tyumen")
+///     .authenticated()
+///     .with_token_provider(TokenCallback::new(|| async {
+///         Ok(SecretToken::new("fetched-token"))
+///     }))
+///     .parser();
+/// ```
+pub struct TokenCallback<F> {
+    callback: F,
+}
+
+impl<F, Fut> TokenCallback<F>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<SecretToken>> + Send + 'static,
+{
+    pub fn new(callback: F) -> Self {
+        Self { callback }
+    }
+}
+
+impl<F, Fut> TokenProvider for TokenCallback<F>
+where
+    F: Fn() -> Fut + Send + Sync,
+    Fut: Future<Output = Result<SecretToken>> + Send + 'static,
+{
+    fn token(&self) -> BoxFuture<'_, Result<SecretToken>> {
+        (self.callback)().boxed()
+    }
+
+    fn can_refresh(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Client, RateLimit, RateLimiter, UpdateGroupsRequest};
+    use mockito::Server;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[tokio::test]
+    async fn test_refreshes_and_retries_once_on_401() {
+        let mut server = Server::new_async().await;
+        let rejected = server
+            .mock("POST", "/parser/groups")
+            .match_header("authorization", "Bearer old")
+            .with_status(401)
+            .expect(1)
+            .create_async()
+            .await;
+        let accepted = server
+            .mock("POST", "/parser/groups")
+            .match_header("authorization", "Bearer new")
+            .with_status(200)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let provider = {
+            let calls = calls.clone();
+            TokenCallback::new(move || {
+                let token = match calls.fetch_add(1, Ordering::SeqCst) {
+                    0 => "old",
+                    _ => "new",
+                };
+                async move { Ok(SecretToken::new(token)) }
+            })
+        };
+        let parser = Client::new(&server.url())
+            .authenticated()
+            .with_token_provider(provider)
+            .parser();
+
+        parser
+            .update_groups(UpdateGroupsRequest {
+                campus_id: 1,
+                student_group_names: vec!["A".to_string()],
+            })
+            .await
+            .unwrap();
+
+        rejected.assert_async().await;
+        accepted.assert_async().await;
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fixed_token_is_not_retried_on_401() {
+        let mut server = Server::new_async().await;
+        let rejected = server
+            .mock("POST", "/parser/groups")
+            .with_status(401)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let parser = Client::new(&server.url())
+            .authenticated()
+            .with_token("token")
+            .parser();
+        let result = parser
+            .update_groups(UpdateGroupsRequest {
+                campus_id: 1,
+                student_group_names: vec!["A".to_string()],
+            })
+            .await;
+
+        rejected.assert_async().await;
+        assert!(matches!(
+            result,
+            Err(crate::Error::Api {
+                status_code: 401,
+                ..
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_401_is_rate_limited() {
+        let mut server = Server::new_async().await;
+        let rejected = server
+            .mock("POST", "/parser/groups")
+            .with_status(401)
+            .expect(2)
+            .create_async()
+            .await;
+
+        let parser = Client::new(&server.url())
+            .with_rate_limiter(RateLimiter::new().parser(RateLimit::new(5.0, 1).unwrap()))
+            .authenticated()
+            .with_token_provider(TokenCallback::new(|| async {
+                Ok(SecretToken::new("token"))
+            }))
+            .parser();
+
+        let started = tokio::time::Instant::now();
+        let result = parser
+            .update_groups(UpdateGroupsRequest {
+                campus_id: 1,
+                student_group_names: vec!["A".to_string()],
+            })
+            .await;
+
+        rejected.assert_async().await;
+        assert!(matches!(
+            result,
+            Err(crate::Error::Api {
+                status_code: 401,
+                ..
+            })
+        ));
+        assert!(started.elapsed() >= std::time::Duration::from_millis(150));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_watched_file_picks_up_rotation() {
+        use std::os::unix::fs::PermissionsExt;
+        use std::time::Duration;
+
+        let path = std::env::temp_dir().join(format!("osars-watched-{}", std::process::id()));
+        std::fs::write(&path, "first").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+
+        let provider = WatchedTokenFile::new(&path);
+        assert_eq!(provider.token().await.unwrap().expose(), "first");
+
+        std::fs::write(&path, "second").unwrap();
+        let file = std::fs::File::options().write(true).open(&path).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(provider.token().await.unwrap().expose(), "second");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
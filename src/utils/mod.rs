@@ -1,2 +1,3 @@
 pub mod date_serde;
 pub mod time_serde;
+pub mod weekday_serde;
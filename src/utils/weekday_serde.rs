@@ -0,0 +1,17 @@
+use crate::models::Weekday;
+use serde::{Deserialize, Deserializer, Serializer, de};
+
+pub fn serialize<S>(weekday: &Weekday, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_u8(weekday.number_from_monday())
+}
+
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Weekday, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let n = u8::deserialize(deserializer)?;
+    Weekday::try_from(n).map_err(de::Error::custom)
+}
@@ -0,0 +1,525 @@
+use crate::error::Result;
+use crate::models::Week;
+use crate::{Client, Lesson, Schedule};
+use chrono::{Local, NaiveDate};
+use futures::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[cfg(feature = "logging")]
+use tracing::{debug, warn};
+
+/// A single difference between two observed versions of a group's schedule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScheduleChange {
+    /// A lesson appeared that was not there before
+    Added {
+        group_id: u32,
+        date: NaiveDate,
+        lesson: Lesson,
+    },
+    /// A previously seen lesson disappeared
+    Removed {
+        group_id: u32,
+        date: NaiveDate,
+        lesson: Lesson,
+    },
+    /// A lesson in the same slot changed its details
+    Changed {
+        group_id: u32,
+        date: NaiveDate,
+        before: Lesson,
+        after: Lesson,
+    },
+}
+
+impl ScheduleChange {
+    /// ID of the group the change belongs to
+    pub fn group_id(&self) -> u32 {
+        match self {
+            ScheduleChange::Added { group_id, .. }
+            | ScheduleChange::Removed { group_id, .. }
+            | ScheduleChange::Changed { group_id, .. } => *group_id,
+        }
+    }
+
+    /// Date of the affected lesson
+    pub fn date(&self) -> NaiveDate {
+        match self {
+            ScheduleChange::Added { date, .. }
+            | ScheduleChange::Removed { date, .. }
+            | ScheduleChange::Changed { date, .. } => *date,
+        }
+    }
+}
+
+/// Computes the changes between two fetched versions of a group's schedule.
+///
+/// `window` is the inclusive date range `current` was fetched for. Lessons
+/// are matched by date and order; dates of `previous` outside the window
+/// have scrolled out of it and are ignored, while days inside it that are
+/// missing from `current` have all their lessons reported as removed.
+pub fn diff_schedules(
+    group_id: u32,
+    previous: &[Schedule],
+    current: &[Schedule],
+    window: (NaiveDate, NaiveDate),
+) -> Vec<ScheduleChange> {
+    let (first, last) = window;
+    let old = slots(previous.iter().filter(|s| (first..=last).contains(&s.date)));
+    let new = slots(current.iter());
+
+    let keys: BTreeSet<_> = old.keys().chain(new.keys()).copied().collect();
+    let mut changes = Vec::new();
+
+    for key @ (date, _) in keys {
+        let mut before = old.get(&key).cloned().unwrap_or_default();
+        let mut after = new.get(&key).cloned().unwrap_or_default();
+
+        // Drop lessons present in both versions, leaving only the differences
+        before.retain(|lesson| match after.iter().position(|l| l == lesson) {
+            Some(index) => {
+                after.remove(index);
+                false
+            }
+            None => true,
+        });
+
+        let mut before = before.into_iter();
+        let mut after = after.into_iter();
+        loop {
+            match (before.next(), after.next()) {
+                (Some(before), Some(after)) => changes.push(ScheduleChange::Changed {
+                    group_id,
+                    date,
+                    before,
+                    after,
+                }),
+                (Some(lesson), None) => changes.push(ScheduleChange::Removed {
+                    group_id,
+                    date,
+                    lesson,
+                }),
+                (None, Some(lesson)) => changes.push(ScheduleChange::Added {
+                    group_id,
+                    date,
+                    lesson,
+                }),
+                (None, None) => break,
+            }
+        }
+    }
+
+    changes
+}
+
+fn slots<'a>(
+    schedules: impl Iterator<Item = &'a Schedule>,
+) -> BTreeMap<(NaiveDate, u32), Vec<Lesson>> {
+    let mut slots: BTreeMap<_, Vec<Lesson>> = BTreeMap::new();
+    for schedule in schedules {
+        for lesson in &schedule.lessons {
+            slots
+                .entry((schedule.date, lesson.order))
+                .or_default()
+                .push(lesson.clone());
+        }
+    }
+    slots
+}
+
+/// Last observed schedules per group, persisted between watcher runs.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WatchState {
+    pub groups: HashMap<u32, Vec<Schedule>>,
+}
+
+impl WatchState {
+    /// Loads state from a JSON file, returning an empty state if it does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(raw) => Ok(serde_json::from_str(&raw)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes state to a JSON file.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let raw = serde_json::to_string(self)?;
+        std::fs::write(path, raw)?;
+        Ok(())
+    }
+}
+
+/// Polls schedules of one or more groups and reports the differences.
+///
+/// # Examples
+///
+/// ```no_run
+/// use futures::StreamExt;
+/// use osars::Client;
+/// use std::time::Duration;
+///
+/// # async fn run() -> osars::Result<()> {
+/// let client = Client::new("https://api.example.com");
+/// let mut changes = Box::pin(
+///     client
+///         .watch([101, 102])
+///         .interval(Duration::from_secs(300))
+///         .state_file("watch-state.json")
+///         .stream()?,
+/// );
+///
+/// while let Some(change) = changes.next().await {
+///     println!("{:?}", change?);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScheduleWatcher {
+    client: Client,
+    group_ids: Vec<u32>,
+    week: Week,
+    interval: Duration,
+    max_backoff: Duration,
+    state_path: Option<PathBuf>,
+}
+
+impl ScheduleWatcher {
+    pub fn new(client: Client, group_ids: impl IntoIterator<Item = u32>) -> Self {
+        Self {
+            client,
+            group_ids: group_ids.into_iter().collect(),
+            week: Week::Current,
+            interval: Duration::from_secs(300),
+            max_backoff: Duration::from_secs(3600),
+            state_path: None,
+        }
+    }
+
+    /// Sets the delay between successful polls
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the upper bound for the delay after consecutive failures
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the week to poll (defaults to `Week::Current`)
+    pub fn week(mut self, week: Week) -> Self {
+        self.week = week;
+        self
+    }
+
+    /// Persists the last seen schedules to a JSON file
+    pub fn state_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_path = Some(path.into());
+        self
+    }
+
+    /// Starts watching and returns a stream of changes.
+    ///
+    /// Groups without a previously seen state report every lesson as added.
+    /// Fetch errors are yielded as `Err` items and delay the next poll with
+    /// exponential backoff.
+    ///
+    /// Delivery is at least once: a group's new state is saved only when the
+    /// stream is polled again after its last change was yielded. If the
+    /// stream is dropped earlier, a restarted watcher reports those changes
+    /// again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the state file exists but cannot be read.
+    pub fn stream(self) -> Result<impl Stream<Item = Result<ScheduleChange>>> {
+        let state = match &self.state_path {
+            Some(path) => WatchState::load(path)?,
+            None => WatchState::default(),
+        };
+
+        let watch = WatchLoop {
+            watcher: self,
+            state,
+            pending: VecDeque::new(),
+            failures: 0,
+            started: false,
+        };
+
+        Ok(futures::stream::unfold(watch, |mut watch| async move {
+            let item = watch.next().await;
+            Some((item, watch))
+        }))
+    }
+}
+
+enum Pending {
+    Item(Result<ScheduleChange>),
+    /// Stores a group's schedules once all its changes have been handed out
+    Commit {
+        group_id: u32,
+        current: Vec<Schedule>,
+    },
+}
+
+struct WatchLoop {
+    watcher: ScheduleWatcher,
+    state: WatchState,
+    pending: VecDeque<Pending>,
+    failures: u32,
+    started: bool,
+}
+
+impl WatchLoop {
+    async fn next(&mut self) -> Result<ScheduleChange> {
+        loop {
+            match self.pending.pop_front() {
+                Some(Pending::Item(item)) => return item,
+                Some(Pending::Commit { group_id, current }) => {
+                    self.commit(group_id, current)?;
+                    continue;
+                }
+                None => {}
+            }
+
+            if self.started {
+                tokio::time::sleep(self.delay()).await;
+            }
+            self.started = true;
+            self.poll().await;
+        }
+    }
+
+    fn delay(&self) -> Duration {
+        let factor = 2u32.saturating_pow(self.failures);
+        self.watcher
+            .interval
+            .saturating_mul(factor)
+            .min(self.watcher.max_backoff.max(self.watcher.interval))
+    }
+
+    async fn poll(&mut self) {
+        let mut failed = false;
+        let window = self.watcher.week.range(Local::now().date_naive());
+
+        for &group_id in &self.watcher.group_ids {
+            let fetched = self
+                .watcher
+                .client
+                .schedule(group_id)
+                .week(self.watcher.week)
+                .send()
+                .await;
+
+            match fetched {
+                Ok(current) => {
+                    let previous = self.state.groups.get(&group_id).map(Vec::as_slice);
+                    let changes =
+                        diff_schedules(group_id, previous.unwrap_or_default(), &current, window);
+                    #[cfg(feature = "logging")]
+                    debug!("Group {}: {} schedule changes", group_id, changes.len());
+                    self.pending
+                        .extend(changes.into_iter().map(|c| Pending::Item(Ok(c))));
+                    self.pending
+                        .push_back(Pending::Commit { group_id, current });
+                }
+                Err(e) => {
+                    #[cfg(feature = "logging")]
+                    warn!("Failed to poll schedule for group {}: {}", group_id, e);
+                    self.pending.push_back(Pending::Item(Err(e)));
+                    failed = true;
+                }
+            }
+        }
+
+        self.failures = if failed { self.failures + 1 } else { 0 };
+    }
+
+    /// Records a group's schedules and saves the state file if they changed
+    fn commit(&mut self, group_id: u32, current: Vec<Schedule>) -> Result<()> {
+        if self.state.groups.get(&group_id) == Some(&current) {
+            return Ok(());
+        }
+        self.state.groups.insert(group_id, current);
+        match &self.watcher.state_path {
+            Some(path) => self.state.save(path),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, NaiveTime};
+    use futures::StreamExt;
+    use mockito::Server;
+
+    fn lesson(order: u32, title: &str) -> Lesson {
+        Lesson {
+            title: title.to_string(),
+            cabinet: "101".to_string(),
+            teacher: "Smith".to_string(),
+            order,
+            start_time: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end_time: NaiveTime::from_hms_opt(10, 30, 0).unwrap(),
+        }
+    }
+
+    fn schedule(day: u32, lessons: Vec<Lesson>) -> Schedule {
+        Schedule {
+            group_id: 1,
+            date: NaiveDate::from_ymd_opt(2024, 1, day).unwrap(),
+            lessons,
+        }
+    }
+
+    /// Week of 2024-01-15, the dates used by `schedule`
+    fn week() -> (NaiveDate, NaiveDate) {
+        Week::Current.range(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap())
+    }
+
+    #[test]
+    fn test_diff_detects_added_removed_changed() {
+        let previous = vec![schedule(15, vec![lesson(1, "Math"), lesson(2, "Physics")])];
+        let current = vec![schedule(
+            15,
+            vec![
+                lesson(1, "Math"),
+                lesson(2, "Chemistry"),
+                lesson(3, "History"),
+            ],
+        )];
+
+        let changes = diff_schedules(1, &previous, &current, week());
+        assert_eq!(changes.len(), 2);
+        assert!(matches!(
+            &changes[0],
+            ScheduleChange::Changed { before, after, .. }
+                if before.title == "Physics" && after.title == "Chemistry"
+        ));
+        assert!(matches!(
+            &changes[1],
+            ScheduleChange::Added { lesson, .. } if lesson.title == "History"
+        ));
+
+        let changes = diff_schedules(1, &current, &previous, week());
+        assert!(matches!(
+            &changes[1],
+            ScheduleChange::Removed { lesson, .. } if lesson.title == "History"
+        ));
+    }
+
+    #[test]
+    fn test_diff_ignores_dates_before_window() {
+        let previous = vec![schedule(15, vec![lesson(1, "Math")])];
+        let current = vec![schedule(22, vec![lesson(1, "Math")])];
+        let next_week = Week::Next.range(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+
+        let changes = diff_schedules(1, &previous, &current, next_week);
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(&changes[0], ScheduleChange::Added { .. }));
+    }
+
+    #[test]
+    fn test_diff_reports_missing_first_day_as_removed() {
+        let previous = vec![
+            schedule(15, vec![lesson(1, "Math")]),
+            schedule(16, vec![lesson(1, "Art")]),
+        ];
+        let current = vec![schedule(16, vec![lesson(1, "Art")])];
+
+        let changes = diff_schedules(1, &previous, &current, week());
+        assert_eq!(changes.len(), 1);
+        assert!(matches!(
+            &changes[0],
+            ScheduleChange::Removed { lesson, date, .. }
+                if lesson.title == "Math" && date.day() == 15
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_watch_persists_state() {
+        let mut server = Server::new_async().await;
+        let monday = Week::Current.range(Local::now().date_naive()).0;
+        let body = serde_json::to_string(&vec![Schedule {
+            date: monday,
+            ..schedule(15, vec![lesson(1, "Math")])
+        }])
+        .unwrap();
+        let mock = server
+            .mock("GET", "/groups/1/schedules?week=current")
+            .with_status(200)
+            .with_body(body)
+            .expect_at_least(4)
+            .create_async()
+            .await;
+
+        let path = std::env::temp_dir().join(format!("osars-watch-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let client = Client::new(&server.url());
+
+        // Dropped before asking for more, so the change is not committed
+        for _ in 0..2 {
+            let mut changes = Box::pin(client.watch([1]).state_file(&path).stream().unwrap());
+            let first = changes.next().await.unwrap().unwrap();
+            assert!(matches!(first, ScheduleChange::Added { group_id: 1, .. }));
+        }
+
+        // Asking for the next change commits the state
+        let mut changes = Box::pin(client.watch([1]).state_file(&path).stream().unwrap());
+        changes.next().await.unwrap().unwrap();
+        let next = tokio::time::timeout(Duration::from_millis(50), changes.next()).await;
+        assert!(next.is_err());
+        drop(changes);
+
+        // A restarted watcher picks up the saved state and sees no changes
+        let mut changes = Box::pin(
+            client
+                .watch([1])
+                .interval(Duration::from_millis(10))
+                .state_file(&path)
+                .stream()
+                .unwrap(),
+        );
+        let next = tokio::time::timeout(Duration::from_millis(200), changes.next()).await;
+        assert!(next.is_err());
+
+        mock.assert_async().await;
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_failing_group_does_not_skip_others() {
+        let mut server = Server::new_async().await;
+        server
+            .mock("GET", "/groups/1/schedules?week=current")
+            .with_status(500)
+            .create_async()
+            .await;
+        let body = serde_json::to_string(&vec![Schedule {
+            group_id: 2,
+            ..schedule(15, vec![lesson(1, "Math")])
+        }])
+        .unwrap();
+        server
+            .mock("GET", "/groups/2/schedules?week=current")
+            .with_body(body)
+            .create_async()
+            .await;
+
+        let client = Client::new(&server.url());
+        let mut changes = Box::pin(client.watch([1, 2]).stream().unwrap());
+
+        assert!(changes.next().await.unwrap().is_err());
+        let next = changes.next().await.unwrap().unwrap();
+        assert!(matches!(next, ScheduleChange::Added { group_id: 2, .. }));
+    }
+}